@@ -0,0 +1,367 @@
+//! This module provides [`Alert`]s produced by checking apps and their
+//! delivery to guilds.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use futures::TryStreamExt;
+use poise::serenity_prelude as serenity;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::{Result, embeds, framework, metrics, models, scheduler, steam, util};
+
+/// Held while delivering queued alerts so checks and the hourly delivery don't
+/// send the same alerts twice.
+static QUEUE_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// A notification about an app to be sent to a guild.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", content = "app", rename_all = "snake_case")]
+pub enum Alert {
+    /// The app went on sale.
+    Sale {
+        app: steam::App,
+        #[serde(default)]
+        ratings: steam::Ratings,
+        /// The sale event running when the sale was found.
+        #[serde(default)]
+        event: Option<String>,
+    },
+    /// The app is no longer coming soon.
+    Released(steam::App),
+    /// The app's sale ends soon.
+    SaleEnding(models::App),
+    /// The app's base price changed from `old_price`.
+    PriceChanged {
+        app: steam::App,
+        old_price: models::Price,
+    },
+    /// The app is no longer available on the store.
+    Delisted(models::App),
+    /// The app is available on the store again.
+    Relisted(steam::App),
+    /// The app entered or left Early Access.
+    EarlyAccessChanged { app: steam::App, entered: bool },
+    /// The coming soon app's release date changed from `old_date`.
+    ReleaseDateChanged { app: steam::App, old_date: String },
+}
+
+impl Alert {
+    pub fn embed(&self) -> serenity::CreateEmbed {
+        match self {
+            Alert::Sale {
+                app,
+                ratings,
+                event,
+            } => embeds::sale_embed(app, ratings, event.as_deref()),
+            Alert::Released(app) => embeds::released_embed(app),
+            Alert::SaleEnding(app) => embeds::sale_ending_embed(app),
+            Alert::PriceChanged { app, old_price } => embeds::price_change_embed(app, old_price),
+            Alert::Delisted(app) => embeds::delisted_embed(app),
+            Alert::Relisted(app) => embeds::relisted_embed(app),
+            Alert::EarlyAccessChanged { app, entered } => embeds::early_access_embed(app, *entered),
+            Alert::ReleaseDateChanged { app, old_date } => {
+                embeds::release_date_change_embed(app, old_date)
+            }
+        }
+    }
+
+    /// The alert's kind for metrics.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Alert::Sale { .. } => "sale",
+            Alert::Released(_) => "released",
+            Alert::SaleEnding(_) => "sale_ending",
+            Alert::PriceChanged { .. } => "price_changed",
+            Alert::Delisted(_) => "delisted",
+            Alert::Relisted(_) => "relisted",
+            Alert::EarlyAccessChanged { .. } => "early_access_changed",
+            Alert::ReleaseDateChanged { .. } => "release_date_changed",
+        }
+    }
+
+    /// Name of the app the alert is about.
+    pub fn app_name(&self) -> &str {
+        match self {
+            Alert::Sale { app, .. }
+            | Alert::Released(app)
+            | Alert::PriceChanged { app, .. }
+            | Alert::Relisted(app)
+            | Alert::EarlyAccessChanged { app, .. }
+            | Alert::ReleaseDateChanged { app, .. } => &app.name,
+            Alert::SaleEnding(app) | Alert::Delisted(app) => &app.app_name,
+        }
+    }
+
+    /// Whether the alert no longer applies at `now`, such as a reminder for a
+    /// sale that already ended.
+    pub fn is_expired_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        match self {
+            Alert::SaleEnding(app) => app
+                .price
+                .as_ref()
+                .and_then(|p| p.discount_end)
+                .is_none_or(|end| util::to_chrono(end) <= now),
+            _ => false,
+        }
+    }
+}
+
+/// Collects alerts per guild so they can be delivered together after a check.
+#[derive(Debug, Default)]
+pub struct Outbox(Mutex<HashMap<i64, Vec<Alert>>>);
+
+impl Outbox {
+    pub fn push(&self, guild_id: i64, alert: Alert) {
+        self.0
+            .lock()
+            .expect("outbox lock shouldn't be poisoned")
+            .entry(guild_id)
+            .or_default()
+            .push(alert);
+    }
+
+    pub fn into_inner(self) -> HashMap<i64, Vec<Alert>> {
+        self.0
+            .into_inner()
+            .expect("outbox lock shouldn't be poisoned")
+    }
+}
+
+/// Weekly guilds are sent a digest on their own schedule instead of alerts.
+fn receives_alerts(discord: &models::Discord) -> bool {
+    !matches!(discord.delivery_mode, models::DeliveryMode::Weekly { .. })
+}
+
+/// Queues `alerts` to be delivered by [`deliver_queued_to_guild`], so they survive
+/// failed deliveries and restarts. Guilds that aren't sent alerts as they happen
+/// have nothing queued.
+pub async fn queue(
+    ctx: &framework::Data,
+    discord: &models::Discord,
+    alerts: &[Alert],
+) -> Result<()> {
+    if alerts.is_empty() || !receives_alerts(discord) {
+        return Ok(());
+    }
+    ctx.repo.queue.add_alerts(discord.server_id, alerts).await?;
+    Ok(())
+}
+
+/// Queues `alerts`, then delivers the guild's queued alerts unless `now` is within
+/// its quiet hours. Returns false without queueing or sending if the guild isn't sent
+/// alerts as they happen.
+pub async fn deliver_or_queue(
+    ctx: &framework::Data,
+    discord: &models::Discord,
+    alerts: &[Alert],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<bool> {
+    if !receives_alerts(discord) {
+        return Ok(false);
+    }
+    queue(ctx, discord, alerts).await?;
+    // Alerts that fail to deliver stay queued and are retried hourly.
+    if let Err(err) = deliver_queued_to_guild(ctx, discord.server_id, now).await {
+        error!(
+            ?err,
+            guild_id = discord.server_id,
+            "Failed to deliver alerts"
+        );
+    }
+    Ok(true)
+}
+
+/// Delivers queued alerts, such as those queued during quiet hours or that failed
+/// to deliver, to guilds outside their quiet hours.
+#[tracing::instrument(level = "error", skip(ctx, cancel))]
+pub async fn deliver_queued(ctx: &framework::Data, cancel: &CancellationToken) -> Result<()> {
+    let now = chrono::Utc::now();
+
+    let guild_ids = ctx.repo.queue.get_queued_guild_ids().await?;
+    if ctx.dry_run {
+        info!(
+            guilds = guild_ids.len(),
+            "Skipping delivering queued alerts in dry run mode"
+        );
+        return Ok(());
+    }
+    for guild_id in guild_ids {
+        if cancel.is_cancelled() {
+            Err(scheduler::Cancelled)?
+        }
+        if let Err(err) = deliver_queued_to_guild(ctx, guild_id, now).await {
+            error!(?err, guild_id, "Failed to deliver queued alerts");
+        }
+    }
+
+    Ok(())
+}
+
+/// Delivers the guild's queued alerts unless `now` is within its quiet hours.
+pub async fn deliver_queued_to_guild(
+    ctx: &framework::Data,
+    guild_id: i64,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    let _guard = QUEUE_LOCK.lock().await;
+    let queued = ctx
+        .repo
+        .queue
+        .get_alerts(guild_id)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    if queued.is_empty() {
+        return Ok(());
+    }
+
+    let Some(discord) = ctx.repo.discord.get_guild(guild_id).await? else {
+        // The guild was removed while alerts were queued.
+        let ids = queued.iter().map(|q| q.id).collect::<Vec<_>>();
+        ctx.repo.queue.remove_alerts(&ids).await?;
+        return Ok(());
+    };
+    // Alerts queued before switching to weekly are kept in case the guild switches back.
+    if discord.is_quiet_at(now) || !receives_alerts(&discord) {
+        return Ok(());
+    }
+
+    let (expired, queued) = queued
+        .into_iter()
+        .partition::<Vec<_>, _>(|q| q.alert.is_expired_at(now));
+    if !expired.is_empty() {
+        let ids = expired.iter().map(|q| q.id).collect::<Vec<_>>();
+        ctx.repo.queue.remove_alerts(&ids).await?;
+    }
+    if queued.is_empty() {
+        return Ok(());
+    }
+
+    info!(guild_id, count = queued.len(), "Delivering queued alerts");
+    send_queued(ctx, &discord, &queued, now).await
+}
+
+/// Sends queued alerts to the guild's bound channel, either individually or as a
+/// digest depending on the guild's [`models::DeliveryMode`]. Guilds announced sale
+/// events are always sent a digest during a sale, but not during festivals.
+///
+/// Alerts are removed from the queue as they're sent, so a failed send doesn't make
+/// those already sent go out again when the rest are retried.
+async fn send_queued(
+    ctx: &framework::Data,
+    discord: &models::Discord,
+    queued: &[models::QueuedAlert],
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    let during_sale = discord.sale_event_announcements && ctx.calendar.sale_at(now).is_some();
+    let as_digest = match discord.delivery_mode {
+        models::DeliveryMode::Weekly { .. } => return Ok(()),
+        _ if during_sale => true,
+        models::DeliveryMode::Individual => false,
+        models::DeliveryMode::Digest => true,
+        models::DeliveryMode::Auto { threshold } => queued.len() > threshold as usize,
+    };
+    let channel = serenity::ChannelId::new(discord.channel_id.try_into()?);
+
+    if !as_digest {
+        for q in queued {
+            channel
+                .send_message(
+                    &ctx.http,
+                    serenity::CreateMessage::new().embed(q.alert.embed()),
+                )
+                .await?;
+            count_sent(&q.alert);
+            ctx.repo.queue.remove_alerts(&[q.id]).await?;
+        }
+        return Ok(());
+    }
+
+    let alerts = queued.iter().map(|q| q.alert.clone()).collect::<Vec<_>>();
+    let ids = queued.iter().map(|q| q.id).collect::<Vec<_>>();
+    for (i, embed) in digest_embeds(&alerts).into_iter().enumerate() {
+        channel
+            .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+            .await?;
+        // A digest's embeds don't map to single alerts, so the digest is removed
+        // once it starts going out rather than risk sending it twice.
+        if i == 0 {
+            ctx.repo.queue.remove_alerts(&ids).await?;
+            alerts.iter().for_each(count_sent);
+        }
+    }
+
+    Ok(())
+}
+
+fn count_sent(alert: &Alert) {
+    metrics::METRICS
+        .alerts_sent
+        .with_label_values(&[alert.kind()])
+        .inc();
+}
+
+/// Describes the alerts each guild would be sent, such as for a dry run.
+pub fn report(alerts: &HashMap<i64, Vec<Alert>>) -> String {
+    if alerts.is_empty() {
+        return "No alerts".to_string();
+    }
+
+    let mut guilds = alerts.iter().collect::<Vec<_>>();
+    guilds.sort_unstable_by_key(|(guild_id, _)| **guild_id);
+
+    let mut report = String::new();
+    for (guild_id, alerts) in guilds {
+        report.push_str(&format!("Guild {guild_id}: {} alerts\n", alerts.len()));
+        for alert in alerts {
+            report.push_str(&format!("- {}: {}\n", alert.kind(), alert.app_name()));
+        }
+    }
+    report
+}
+
+fn digest_embeds(alerts: &[Alert]) -> Vec<serenity::CreateEmbed> {
+    let mut event = None;
+    let mut sales = Vec::new();
+    let mut releases = Vec::new();
+    let mut ending = Vec::new();
+    let mut price_changes = Vec::new();
+    // State changes are rare enough to be sent as is.
+    let mut others = Vec::new();
+    for alert in alerts {
+        match alert {
+            Alert::Sale {
+                app,
+                event: sale_event,
+                ..
+            } => {
+                event = event.or(sale_event.as_deref());
+                sales.push(app.clone().into());
+            }
+            Alert::Released(app) => releases.push(app.clone().into()),
+            Alert::SaleEnding(app) => ending.push(app.clone()),
+            Alert::PriceChanged { app, old_price } => {
+                price_changes.push((app.clone().into(), old_price.clone()))
+            }
+            Alert::Delisted(_)
+            | Alert::Relisted(_)
+            | Alert::EarlyAccessChanged { .. }
+            | Alert::ReleaseDateChanged { .. } => others.push(alert.embed()),
+        }
+    }
+
+    let title = match event {
+        Some(event) => format!("{event} Digest"),
+        None => "Sale Digest".to_string(),
+    };
+    let mut embeds = embeds::sale_digest_embeds(&title, &sales);
+    embeds.extend(embeds::release_digest_embeds("New Releases", &releases));
+    embeds.extend(embeds::sale_digest_embeds("Last Chance", &ending));
+    embeds.extend(embeds::price_change_digest_embeds(
+        "Price Changes",
+        &price_changes,
+    ));
+    embeds.extend(others);
+    embeds
+}
@@ -0,0 +1,69 @@
+//! This module provides [`check_bindings`] for finding guilds whose bound channel
+//! can no longer be sent alerts.
+
+use futures::StreamExt;
+use poise::serenity_prelude as serenity;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::{Result, framework, metrics, scheduler};
+
+/// Checks that each bound guild's channel still exists and is visible to the bot.
+/// Broken bindings are logged and counted in [`metrics::Metrics::broken_bindings`].
+#[tracing::instrument(level = "error", skip(ctx, cancel))]
+pub async fn check_bindings(ctx: &framework::Data, cancel: &CancellationToken) -> Result<()> {
+    let mut broken = 0;
+
+    let mut guilds = ctx.repo.discord.get_bound_guilds().await?;
+    while let Some(discord) = guilds.next().await {
+        if cancel.is_cancelled() {
+            Err(scheduler::Cancelled)?
+        }
+        let discord = match discord {
+            Ok(x) => x,
+            Err(err) => {
+                error!(?err, "Failed to get guild");
+                continue;
+            }
+        };
+        let channel = match discord.channel_id.try_into() {
+            Ok(id) => serenity::ChannelId::new(id),
+            Err(err) => {
+                error!(
+                    ?err,
+                    guild_id = discord.server_id,
+                    channel_id = discord.channel_id,
+                    "Invalid bound channel"
+                );
+                continue;
+            }
+        };
+        match channel.to_channel(&ctx.http).await {
+            Ok(_) => {}
+            Err(serenity::Error::Http(err)) if is_missing_or_forbidden(&err) => {
+                warn!(
+                    guild_id = discord.server_id,
+                    channel_id = discord.channel_id,
+                    "Bound channel is missing or inaccessible"
+                );
+                broken += 1;
+            }
+            // Unknown failures aren't counted since the channel may be fine.
+            Err(err) => error!(
+                ?err,
+                guild_id = discord.server_id,
+                "Failed to check bound channel"
+            ),
+        }
+    }
+
+    info!(broken, "Checked bindings");
+    metrics::METRICS.broken_bindings.set(broken);
+
+    Ok(())
+}
+
+fn is_missing_or_forbidden(err: &serenity::HttpError) -> bool {
+    err.status_code()
+        .is_some_and(|status| matches!(status.as_u16(), 403 | 404))
+}
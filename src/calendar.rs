@@ -0,0 +1,176 @@
+//! This module provides [`SaleCalendar`] of Steam's seasonal sale events and
+//! their announcements to guilds.
+
+use futures::StreamExt;
+use mongodb::bson;
+use poise::serenity_prelude as serenity;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::{Result, embeds, framework, models, scheduler, util};
+
+/// Calendar used when one isn't configured.
+const BUNDLED_CALENDAR: &str = include_str!("sale_calendar.json");
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct SaleEvent {
+    pub name: String,
+    pub start: chrono::DateTime<chrono::Utc>,
+    pub end: chrono::DateTime<chrono::Utc>,
+    #[serde(default)]
+    pub kind: EventKind,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    /// A seasonal sale with store-wide discounts.
+    #[default]
+    Sale,
+    /// An event without store-wide discounts, such as Next Fest.
+    Festival,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SaleCalendar {
+    events: Vec<SaleEvent>,
+}
+
+impl SaleCalendar {
+    /// Loads the calendar from the JSON in `SALE_CALENDAR` (or `SALE_CALENDAR_FILE`),
+    /// falling back to the bundled calendar if unset.
+    pub fn load() -> Result<Self> {
+        let json = match util::env_var::<String>("SALE_CALENDAR") {
+            Ok(x) => x,
+            Err(util::EnvVarError::InvalidOrMissingKey { .. }) => BUNDLED_CALENDAR.to_string(),
+            Err(err) => Err(err)?,
+        };
+        Ok(Self::from_json(&json)?)
+    }
+
+    /// Parses a calendar from a JSON array of [`SaleEvent`]s.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        Ok(Self {
+            events: serde_json::from_str(json)?,
+        })
+    }
+
+    /// Gets the event running at `now`, if any.
+    pub fn event_at(&self, now: chrono::DateTime<chrono::Utc>) -> Option<&SaleEvent> {
+        self.events.iter().find(|e| e.start <= now && now < e.end)
+    }
+
+    /// Gets the sale running at `now`, if any. Festivals aren't sales.
+    pub fn sale_at(&self, now: chrono::DateTime<chrono::Utc>) -> Option<&SaleEvent> {
+        self.events
+            .iter()
+            .find(|e| e.kind == EventKind::Sale && e.start <= now && now < e.end)
+    }
+}
+
+/// Announces the running sale event to opted-in guilds that weren't already
+/// told about it.
+#[tracing::instrument(level = "error", skip(ctx, cancel))]
+pub async fn announce_sale_event(ctx: &framework::Data, cancel: &CancellationToken) -> Result<()> {
+    // Guilds opting in later in an event aren't told about it.
+    const ANNOUNCE_WINDOW: chrono::TimeDelta = chrono::TimeDelta::days(1);
+
+    let now = chrono::Utc::now();
+    let Some(event) = ctx.calendar.event_at(now) else {
+        return Ok(());
+    };
+    if now - event.start > ANNOUNCE_WINDOW {
+        return Ok(());
+    }
+
+    let mut guilds = ctx.repo.discord.get_sale_event_guilds().await?;
+    while let Some(discord) = guilds.next().await {
+        if cancel.is_cancelled() {
+            Err(scheduler::Cancelled)?
+        }
+        let discord = match discord {
+            Ok(x) => x,
+            Err(err) => {
+                error!(?err, "Failed to get guild");
+                continue;
+            }
+        };
+        // Quiet guilds are retried next hour.
+        let start = util::from_chrono(event.start);
+        if discord.announced_event_start == Some(start) || discord.is_quiet_at(now) {
+            continue;
+        }
+
+        if ctx.dry_run {
+            info!(
+                guild_id = discord.server_id,
+                event = event.name,
+                "Would announce sale event"
+            );
+            continue;
+        }
+        info!(
+            guild_id = discord.server_id,
+            event = event.name,
+            "Announcing sale event"
+        );
+        if let Err(err) = announce_to_guild(ctx, &discord, event, start).await {
+            error!(
+                ?err,
+                guild_id = discord.server_id,
+                "Failed to announce sale event"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn announce_to_guild(
+    ctx: &framework::Data,
+    discord: &models::Discord,
+    event: &SaleEvent,
+    start: bson::DateTime,
+) -> Result<()> {
+    let channel = serenity::ChannelId::new(discord.channel_id.try_into()?);
+    let message = serenity::CreateMessage::new().embed(embeds::sale_event_embed(event));
+    channel.send_message(&ctx.http, message).await?;
+    ctx.repo
+        .discord
+        .set_announced_event_start(discord.server_id, start)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::calendar::{BUNDLED_CALENDAR, EventKind, SaleCalendar};
+
+    #[test]
+    fn bundled_calendar_parses() {
+        let calendar = SaleCalendar::from_json(BUNDLED_CALENDAR).unwrap();
+        assert!(!calendar.events.is_empty());
+    }
+
+    #[test]
+    fn sale_at_skips_festivals() {
+        let calendar = SaleCalendar::from_json(
+            r#"[
+                { "name": "Fest", "start": "2026-01-01T00:00:00Z", "end": "2026-01-10T00:00:00Z", "kind": "festival" },
+                { "name": "Sale", "start": "2026-01-05T00:00:00Z", "end": "2026-01-15T00:00:00Z" }
+            ]"#,
+        )
+        .unwrap();
+        let at = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+
+        let fest = calendar.event_at(at("2026-01-02T00:00:00Z")).unwrap();
+        assert_eq!(EventKind::Festival, fest.kind);
+        assert_eq!(None, calendar.sale_at(at("2026-01-02T00:00:00Z")));
+
+        let sale = calendar.sale_at(at("2026-01-06T00:00:00Z")).unwrap();
+        assert_eq!("Sale", sale.name);
+        assert_eq!(EventKind::Sale, sale.kind);
+    }
+}
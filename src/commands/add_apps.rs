@@ -1,11 +1,9 @@
 use anyhow::Context;
-use futures::{StreamExt, stream};
 use poise::serenity_prelude as serenity;
 use tracing::error;
 
 use crate::{
-    Result, config, framework, models, repos,
-    steam::{self, FetchError},
+    Result, config, framework, models, repos, steam,
     util::{self, ToReply},
 };
 
@@ -29,49 +27,29 @@ pub async fn add_apps(
 
     let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
 
-    let (apps, rate_limited) = fetch_apps(&ctx.data().steam, app_ids.clone()).await;
-    let added_apps = add_apps_to_db(&ctx.data().repo, guild_id, &apps, threshold).await;
-    let failed_apps = app_ids
-        .into_iter()
-        .filter(|&app_id| !added_apps.iter().any(|app| app.app_id == app_id))
-        .collect::<Vec<i32>>();
-
-    let reply = create_reply(added_apps, failed_apps, rate_limited);
+    let reply = fetch_and_add_apps(ctx.data(), guild_id, app_ids, threshold).await;
     ctx.send(reply).await?;
 
     Ok(())
 }
 
-async fn fetch_apps(steam: &steam::Client, app_ids: Vec<i32>) -> (Vec<steam::App>, bool) {
-    const FETCH_BUFFER_SIZE: usize = 5;
-
-    let fetches = stream::iter(app_ids.into_iter().map(|app_id| {
-        let steam = steam.clone();
-        async move { (app_id, steam.app_details(app_id).await) }
-    }));
-    let mut fetch_stream = fetches.buffer_unordered(FETCH_BUFFER_SIZE);
-
-    let mut apps = Vec::new();
-    let mut rate_limited = false;
-    while let Some((app_id, app)) = fetch_stream.next().await {
-        match app {
-            Ok(Some(app)) => {
-                if !app.is_free || app.release_date.coming_soon {
-                    apps.push(app);
-                }
-            }
-            Ok(None) => { /* App doesn't exist for given app_id */ }
-            Err(FetchError::Http(err))
-                if err.status() == Some(reqwest::StatusCode::TOO_MANY_REQUESTS) =>
-            {
-                rate_limited = true;
-                break;
-            }
-            Err(err) => error!(app_id, ?err, "Failed to fetch app"),
-        }
-    }
+/// Fetches the apps and adds those that are trackable to the guild, replying
+/// with which were added and which failed.
+pub(super) async fn fetch_and_add_apps(
+    data: &framework::Data,
+    guild_id: i64,
+    app_ids: Vec<i32>,
+    threshold: Option<i32>,
+) -> poise::CreateReply {
+    let (mut apps, rate_limited) = data.steam.app_details_batch(app_ids.clone()).await;
+    apps.retain(|app| !app.is_free || app.release_date.coming_soon);
+    let added_apps = add_apps_to_db(&data.repo, guild_id, &apps, threshold).await;
+    let failed_apps = app_ids
+        .into_iter()
+        .filter(|&app_id| !added_apps.iter().any(|app| app.app_id == app_id))
+        .collect::<Vec<i32>>();
 
-    (apps, rate_limited)
+    create_reply(added_apps, failed_apps, rate_limited)
 }
 
 async fn add_apps_to_db<'a>(
@@ -101,6 +79,7 @@ async fn add_apps_to_db<'a>(
             is_trailing_sale_day: false,
             coming_soon: app.release_date.coming_soon,
             sale_threshold: threshold,
+            ..Default::default()
         };
 
         if repo
@@ -137,22 +116,18 @@ fn create_reply(
 ) -> poise::CreateReply {
     let mut embed = serenity::CreateEmbed::new()
         .title("Add Apps")
-        .color(config::BRAND_DARK_COLOR);
+        .color(config::get().discord.brand_color);
 
     if !added_apps.is_empty() {
-        let success_body = added_apps
-            .iter()
-            .map(|app| format!("{} ({})", app.name, app.app_id))
-            .collect::<Vec<String>>()
-            .join("\n");
+        let success_body = field_list(
+            added_apps
+                .iter()
+                .map(|app| format!("{} ({})", app.name, app.app_id)),
+        );
         embed = embed.field("Successfully Added", success_body, false);
     }
     if !failed_apps.is_empty() {
-        let fail_body = failed_apps
-            .iter()
-            .map(|id| id.to_string())
-            .collect::<Vec<String>>()
-            .join("\n");
+        let fail_body = field_list(failed_apps.iter().map(|id| id.to_string()));
         embed = embed.field("Failed to Add", fail_body, false);
 
         let footer = if rate_limited {
@@ -166,3 +141,29 @@ fn create_reply(
 
     embed.to_reply()
 }
+
+/// Joins `lines` into an embed field value, cutting off lines that would exceed
+/// the field's length limit.
+fn field_list(lines: impl ExactSizeIterator<Item = String>) -> String {
+    const MAX_FIELD_LEN: usize = 1024;
+    // Leaves room for the "...and N more" line.
+    const MAX_LISTED_LEN: usize = MAX_FIELD_LEN - 32;
+
+    let total = lines.len();
+    let mut listed = 0;
+    let mut body = String::new();
+    for line in lines {
+        if body.len() + line.len() + 1 > MAX_LISTED_LEN {
+            break;
+        }
+        if !body.is_empty() {
+            body.push('\n');
+        }
+        body.push_str(&line);
+        listed += 1;
+    }
+    if listed < total {
+        body.push_str(&format!("\n...and {} more", total - listed));
+    }
+    body
+}
@@ -0,0 +1,59 @@
+use anyhow::Context;
+
+use super::add_apps::fetch_and_add_apps;
+use crate::{Result, framework};
+
+/// Max DLC fetched at once so big franchises don't exhaust Steam's rate limit.
+const MAX_DLC: usize = 25;
+
+/// Adds all DLC of a game to the tracker.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn add_dlc(
+    ctx: framework::Context<'_>,
+    #[rename = "appid"]
+    #[description = "App ID of the base game"]
+    #[min = 1]
+    app_id: i32,
+    #[min = 1]
+    #[max = 99]
+    threshold: Option<i32>,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+
+    let mut app = match ctx.data().steam.app_details(app_id).await {
+        Ok(Some(app)) => app,
+        Ok(None) => {
+            ctx.say(format!("Couldn't find an app with appid {app_id}."))
+                .await?;
+            return Ok(());
+        }
+        Err(err) if err.is_rate_limited() => {
+            ctx.say(
+                "Bot was rate-limited by Steam. Please wait a few minutes before trying again!",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(err) => Err(err)?,
+    };
+    if app.dlc.is_empty() {
+        ctx.say(format!("{} has no DLC.", app.name)).await?;
+        return Ok(());
+    }
+
+    let total = app.dlc.len();
+    app.dlc.truncate(MAX_DLC);
+    let mut reply = fetch_and_add_apps(ctx.data(), guild_id, app.dlc, threshold).await;
+    if total > MAX_DLC {
+        reply = reply.content(format!(
+            "{} has {total} DLC. Showing the first {MAX_DLC}; add the rest with /add_apps.",
+            app.name
+        ));
+    }
+    ctx.send(reply).await?;
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use futures::StreamExt;
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use super::modals::ThresholdModal;
+use crate::{Result, config, embeds, framework, models, steam, util};
+
+/// Show details of a tracked app.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn app_info(
+    ctx: framework::Context<'_>,
+    #[rename = "appid"]
+    #[min = 0]
+    app_id: i32,
+) -> Result<()> {
+    ctx.defer().await?;
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+    let repo = &ctx.data().repo;
+
+    let Some(mut junction) = repo.junction.get_junction(guild_id, app_id).await? else {
+        ctx.say("That app isn't being tracked.").await?;
+        return Ok(());
+    };
+    let models::Discord { sale_threshold, .. } = repo
+        .discord
+        .get_guild(guild_id)
+        .await?
+        .with_context(|| anyhow::anyhow!("Missing Discord record for guild_id={guild_id}"))?;
+
+    let app = match ctx.data().steam.app_details(app_id).await {
+        Ok(Some(app)) => app,
+        Ok(None) => {
+            ctx.say("Couldn't get more details on the app. It may not be available in the US.")
+                .await?;
+            return Ok(());
+        }
+        Err(err) if err.is_rate_limited() => {
+            ctx.say(
+                "Bot was rate-limited by Steam. Please wait a few minutes before trying again!",
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(err) => Err(err)?,
+    };
+    repo.apps
+        .upsert_app(&app.clone().into())
+        .await
+        .inspect_err(|err| error!(?err, app_id, "Failed to refresh app"))
+        .ok();
+
+    let id = ctx.id().to_string();
+    let untrack_button_id = format!("{id}untrack");
+    let threshold_button_id = format!("{id}threshold");
+
+    let reply = poise::CreateReply::default()
+        .embed(create_embed(&app, &junction, sale_threshold))
+        .components(vec![serenity::CreateActionRow::Buttons(vec![
+            serenity::CreateButton::new(&threshold_button_id)
+                .label("Set Threshold")
+                .style(serenity::ButtonStyle::Primary),
+            serenity::CreateButton::new(&untrack_button_id)
+                .label("Untrack")
+                .style(serenity::ButtonStyle::Danger),
+        ])]);
+    let handle = ctx.send(reply).await?;
+
+    let mut listener = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .custom_ids(vec![untrack_button_id.clone(), threshold_button_id.clone()])
+        .timeout(Duration::from_secs(300))
+        .stream();
+    while let Some(event) = listener.next().await {
+        if event.data.custom_id == untrack_button_id {
+            repo.junction.remove_junction(guild_id, app_id).await?;
+
+            let embed = serenity::CreateEmbed::new()
+                .title(&app.name)
+                .description("No longer tracking app.")
+                .color(config::get().discord.brand_color);
+            let update = serenity::CreateInteractionResponse::UpdateMessage(
+                serenity::CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(Vec::new()),
+            );
+            event.create_response(&ctx, update).await?;
+            break;
+        }
+
+        let Some(modal) = poise::execute_modal_on_component_interaction::<ThresholdModal>(
+            ctx,
+            event,
+            None,
+            Some(Duration::from_secs(300)),
+        )
+        .await?
+        else {
+            continue;
+        };
+        let Some(threshold) = modal.parse_threshold() else {
+            let reply = poise::CreateReply::default()
+                .content("Threshold must be a number from 1 to 99.")
+                .ephemeral(true);
+            ctx.send(reply).await?;
+            continue;
+        };
+
+        let failed = repo
+            .junction
+            .set_thresholds(guild_id, threshold, [app_id])
+            .await;
+        if !failed.is_empty() {
+            let reply = poise::CreateReply::default()
+                .content("Failed to update threshold. Please try again.")
+                .ephemeral(true);
+            ctx.send(reply).await?;
+            continue;
+        }
+
+        junction.sale_threshold = Some(threshold);
+        let edit =
+            poise::CreateReply::default().embed(create_embed(&app, &junction, sale_threshold));
+        handle.edit(ctx, edit).await?;
+    }
+
+    Ok(())
+}
+
+fn create_embed(
+    app: &steam::App,
+    junction: &models::Junction,
+    guild_threshold: i32,
+) -> serenity::CreateEmbed {
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    let price = match &app.price_overview {
+        Some(p) if p.discount_percent > 0 => format!(
+            "~~{}~~ {} (-{}%)",
+            p.initial_formatted, p.final_formatted, p.discount_percent
+        ),
+        Some(p) => p.final_formatted.clone(),
+        None if app.is_free => "Free".to_string(),
+        None => "Unavailable".to_string(),
+    };
+    let release_date = if app.release_date.date.is_empty() {
+        "Unknown".to_string()
+    } else {
+        app.release_date.date.clone()
+    };
+    let threshold = match junction.sale_threshold {
+        Some(threshold) => format!("{threshold}%"),
+        None => format!("{guild_threshold}% (server default)"),
+    };
+
+    let mut fields = vec![("Price", price, true), ("Release Date", release_date, true)];
+    if let Some(recs) = &app.recommendations {
+        fields.push(("Reviews", recs.total.to_string(), true));
+    }
+    if !app.developers.is_empty() {
+        fields.push(("Developers", app.developers.join(", "), true));
+    }
+    fields.extend([
+        ("Discount Threshold", threshold, true),
+        (
+            "Target Price",
+            junction
+                .target_price
+                .map(util::format_price)
+                .unwrap_or("None".to_string()),
+            true,
+        ),
+        ("Coming Soon", yes_no(junction.coming_soon), true),
+        (
+            "Sale Alert Sent",
+            yes_no(junction.is_trailing_sale_day),
+            true,
+        ),
+    ]);
+    if !app.description.is_empty() {
+        fields.push(("Description", app.description.clone(), false));
+    }
+
+    let color = match &app.price_overview {
+        Some(p) if p.discount_percent > 0 => embeds::sale_color(p.discount_percent),
+        _ => config::get().discord.brand_color.0,
+    };
+
+    serenity::CreateEmbed::new()
+        .title(&app.name)
+        .url(url)
+        .image(&app.header_image)
+        .fields(fields)
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "App ID: {}",
+            app.app_id
+        )))
+        .color(color)
+}
+
+fn yes_no(x: bool) -> String {
+    if x { "Yes" } else { "No" }.to_string()
+}
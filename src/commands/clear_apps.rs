@@ -44,7 +44,7 @@ fn create_confirmation_dropdown(id: impl Into<String>) -> poise::CreateReply {
     let embed = serenity::CreateEmbed::new()
         .title("Clear Tracked Apps?")
         .description("Are you sure you want to clear tracked apps?")
-        .color(config::BRAND_DARK_COLOR);
+        .color(config::get().discord.brand_color);
 
     let button = serenity::CreateSelectMenu::new(
         id,
@@ -109,7 +109,7 @@ fn create_edit(description: impl Into<String>) -> serenity::EditInteractionRespo
     let embed = serenity::CreateEmbed::new()
         .title("Clear Tracked Apps?")
         .description(description.into())
-        .color(config::BRAND_DARK_COLOR);
+        .color(config::get().discord.brand_color);
 
     serenity::EditInteractionResponse::new()
         .embed(embed)
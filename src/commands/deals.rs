@@ -0,0 +1,153 @@
+use anyhow::Context;
+use futures::TryStreamExt;
+use poise::serenity_prelude as serenity;
+use tracing::error;
+
+use super::paginate::paginate;
+use crate::{Result, config, embeds, framework, models, util};
+
+/// How long a stored price is trusted before it is re-fetched from Steam.
+const STALE_AFTER: chrono::Duration = chrono::Duration::hours(12);
+
+#[derive(Debug, Default, Clone, Copy, poise::ChoiceParameter)]
+pub enum DealsSort {
+    #[default]
+    Discount,
+    Price,
+}
+
+/// Show tracked apps that are currently on sale.
+#[poise::command(slash_command, user_cooldown = 10)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn deals(
+    ctx: framework::Context<'_>,
+    #[description = "Order deals by highest discount or lowest price"] sort: Option<DealsSort>,
+) -> Result<()> {
+    ctx.defer().await?;
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+
+    let (apps, rate_limited) = get_tracked_apps(ctx.data(), guild_id).await?;
+    if apps.is_empty() {
+        ctx.say("No apps currently being tracked.").await?;
+        return Ok(());
+    }
+
+    let mut deals = apps
+        .into_iter()
+        .filter_map(|app| match app.price {
+            Some(price) if price.discount_percent > 0 => Some((app.app_id, app.app_name, price)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if deals.is_empty() {
+        ctx.say("None of the tracked apps are currently on sale.")
+            .await?;
+        return Ok(());
+    }
+    deals.sort_unstable_by(|a, b| a.1.cmp(&b.1));
+    match sort.unwrap_or_default() {
+        DealsSort::Discount => deals.sort_by_key(|(.., p)| std::cmp::Reverse(p.discount_percent)),
+        DealsSort::Price => deals.sort_by_key(|(.., p)| p.final_price),
+    }
+
+    let pages = deals
+        .chunks(config::get().commands.page_size)
+        .collect::<Vec<_>>();
+    paginate(&ctx, pages.len(), |page| {
+        create_embed(page, &pages, rate_limited)
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Gets the apps tracked by the guild, refreshing apps whose stored price is stale.
+/// Apps that couldn't be refreshed keep their last-known price. The returned bool
+/// is true if refreshing was cut short by Steam's rate-limit.
+async fn get_tracked_apps(
+    data: &framework::Data,
+    guild_id: i64,
+) -> Result<(Vec<models::App>, bool)> {
+    let app_ids = data
+        .repo
+        .junction
+        .get_guild_junctions(guild_id)
+        .await?
+        .map_ok(|j| j.app_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let mut apps = data
+        .repo
+        .apps
+        .get_apps(&app_ids)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let stale_before = chrono::Utc::now() - STALE_AFTER;
+    let stale_ids = apps
+        .iter()
+        .filter(|app| {
+            app.updated_at
+                .is_none_or(|t| util::to_chrono(t) < stale_before)
+        })
+        .map(|app| app.app_id)
+        .collect::<Vec<_>>();
+    if stale_ids.is_empty() {
+        return Ok((apps, false));
+    }
+
+    let (fetched, rate_limited) = data.steam.app_details_batch(stale_ids).await;
+    for fetched in fetched {
+        let fetched: models::App = fetched.into();
+        data.repo
+            .apps
+            .upsert_app(&fetched)
+            .await
+            .inspect_err(|err| error!(?err, app_id = fetched.app_id, "Failed to refresh app"))
+            .ok();
+        if let Some(app) = apps.iter_mut().find(|app| app.app_id == fetched.app_id) {
+            *app = models::App {
+                id: app.id,
+                ..fetched
+            };
+        }
+    }
+
+    Ok((apps, rate_limited))
+}
+
+fn create_embed(
+    current_page: usize,
+    pages: &[&[(i32, String, models::Price)]],
+    rate_limited: bool,
+) -> serenity::CreateEmbed {
+    let page = pages[current_page];
+    let description = page
+        .iter()
+        .map(|(app_id, app_name, price)| {
+            format!(
+                "[{app_name}](https://store.steampowered.com/app/{app_id}) **-{}%** ~~{}~~ {}",
+                price.discount_percent, price.initial_formatted, price.final_formatted
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let top_discount = page
+        .iter()
+        .map(|(.., price)| price.discount_percent)
+        .max()
+        .unwrap_or_default();
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(format!("Deals {}/{}", current_page + 1, pages.len()))
+        .description(description)
+        .color(embeds::sale_color(top_discount));
+    if rate_limited {
+        embed = embed.footer(serenity::CreateEmbedFooter::new(
+            "Bot was rate-limited by Steam. Some prices may be outdated.",
+        ));
+    }
+
+    embed
+}
@@ -22,12 +22,61 @@ pub async fn help(ctx: Context<'_>) -> Result<()> {
                     App IDs can be referenced that this threshold specifically applies to.",
                     false,
                 )
+                .field(
+                    "/set_target_price <appid1, appid2, ...> <price>",
+                    "Also alert when an app's sale price is at or below the given price, \
+                    whichever of the discount threshold or target price is met first. \
+                    Omit the price to remove it.",
+                    false,
+                )
+                .field(
+                    "/set_delivery_mode <mode> <threshold> <weekday> <hour>",
+                    "Set whether alerts are sent individually or grouped into a digest. \
+                    With auto, a digest is sent when a check has more alerts than the threshold. \
+                    With weekly, only a weekly digest is sent on the given weekday and hour.",
+                    false,
+                )
+                .field(
+                    "/set_timezone <timezone>",
+                    "Set the server's timezone used for scheduled deliveries and quiet hours. UTC by default.",
+                    false,
+                )
+                .field(
+                    "/set_quiet_hours <start> <end>",
+                    "Hold back alerts between the given hours in the server's timezone and send \
+                    them once quiet hours end. Omit both hours to disable.",
+                    false,
+                )
+                .field(
+                    "/set_sale_end_reminder <hours>",
+                    "Send a last chance reminder the given hours before an alerted sale ends. \
+                    Only works for sales where Steam provides an end date. Omit hours to disable.",
+                    false,
+                )
+                .field(
+                    "/set_alert_options <price_changes> <auto_untrack_delisted> \
+                    <min_review_percent> <require_deck_playable> <sale_events>",
+                    "Turn optional alerts and filters on or off. \
+                    With price_changes, an alert is sent when a tracked app's base price changes. \
+                    With auto_untrack_delisted, apps are untracked once alerted as delisted. \
+                    With min_review_percent and require_deck_playable, sale alerts are skipped \
+                    for apps with too few positive reviews or without Steam Deck support. \
+                    With sale_events, the start of Steam's seasonal sales is announced. \
+                    During them, alerts are grouped into a digest unless the delivery mode is weekly.",
+                    false,
+                )
                 .field(
                     "/add_apps <appid1, appid2, ...> <threshold>",
                     "Add apps to the tracker. \
                     A discount threshold can be stated that applies specifically to these apps.",
                     false,
                 )
+                .field(
+                    "/add_dlc <appid> <threshold>",
+                    "Add all DLC of a game to the tracker. \
+                    A discount threshold can be stated that applies specifically to the DLC.",
+                    false,
+                )
                 .field(
                     "/remove_apps <appid1, appid2, ...>",
                     "Remove apps from the tracker.",
@@ -39,8 +88,22 @@ pub async fn help(ctx: Context<'_>) -> Result<()> {
                     false,
                 )
                 .field(
-                    "/list_apps",
-                    "List apps being tracked and their discount thresholds.",
+                    "/list_apps <sort> <show_release_dates>",
+                    "List apps being tracked, their discount thresholds and current discounts, \
+                    and optionally the release dates of upcoming apps. \
+                    Apps can be sorted, filtered, removed or have their thresholds changed.",
+                    false,
+                )
+                .field(
+                    "/deals <sort>",
+                    "List tracked apps that are currently on sale. \
+                    Deals can be sorted by discount or by price.",
+                    false,
+                )
+                .field(
+                    "/app_info <appid>",
+                    "Show details of a tracked app, including its price and discount threshold. \
+                    The app can be untracked or have its threshold changed from here.",
                     false,
                 )
                 .field("/clear_apps", "Remove all apps from the tracker.", false)
@@ -59,7 +122,7 @@ pub async fn help(ctx: Context<'_>) -> Result<()> {
                     "Report an issue [here](https://github.com/jasonly027/steamsale_bot/issues).",
                     true,
                 )
-                .color(config::BRAND_DARK_COLOR),
+                .color(config::get().discord.brand_color),
         ),
     )
     .await?;
@@ -1,47 +1,83 @@
 use std::time::Duration;
 
-use anyhow::Context;
+use anyhow::{Context, bail};
 use futures::StreamExt;
 use poise::serenity_prelude as serenity;
+use strum::IntoEnumIterator;
 
-use crate::{Result, config, framework, models};
+use super::modals::ThresholdModal;
+use crate::{Result, config, framework, models, steam};
 
-const PAGE_SIZE: usize = 10;
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    poise::ChoiceParameter,
+    strum_macros::Display,
+    strum_macros::EnumIter,
+    strum_macros::EnumString,
+)]
+pub enum ListSort {
+    #[default]
+    Name,
+    #[name = "App ID"]
+    #[strum(serialize = "App ID")]
+    AppId,
+    Threshold,
+    Discount,
+    #[name = "Date Added"]
+    #[strum(serialize = "Date Added")]
+    DateAdded,
+    #[name = "Release Date"]
+    #[strum(serialize = "Release Date")]
+    ReleaseDate,
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Filter Apps"]
+struct FilterModal {
+    #[name = "Name or app ID (leave blank to clear)"]
+    #[max_length = 100]
+    query: Option<String>,
+}
 
-/// List apps being tracked and their discount thresholds.
+/// List and manage apps being tracked and their discount thresholds.
 #[poise::command(slash_command, user_cooldown = 3)]
 #[tracing::instrument(level = "error", skip(ctx))]
-pub async fn list_apps(ctx: framework::Context<'_>) -> Result<()> {
+pub async fn list_apps(
+    ctx: framework::Context<'_>,
+    #[description = "How apps are initially ordered"] sort: Option<ListSort>,
+    #[description = "Show release dates of upcoming apps"] show_release_dates: Option<bool>,
+) -> Result<()> {
     ctx.defer().await?;
     let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
 
-    let Some(listings) = get_app_listings(&ctx, guild_id).await? else {
+    let listings = ctx.data().repo.junction.get_app_listings(guild_id).await?;
+    if listings.is_empty() {
+        ctx.say("No apps currently being tracked.").await?;
         return Ok(());
+    }
+    let guild_threshold = get_guild_sale_threshold(&ctx, guild_id).await?;
+
+    let mut state = ListState {
+        listings,
+        guild_threshold,
+        sort: sort.unwrap_or_default(),
+        show_release_dates: show_release_dates.unwrap_or_default(),
+        filter: None,
+        page: 0,
+        selected: Vec::new(),
     };
-    let pages = listings.chunks(PAGE_SIZE).collect::<Vec<_>>();
-    let sale_threshold = get_guild_sale_threshold(&ctx, guild_id).await?;
+    state.sort_listings();
 
-    paginate(&ctx, &pages, sale_threshold).await?;
+    manage(&ctx, guild_id, state).await?;
 
     Ok(())
 }
 
-async fn get_app_listings(
-    ctx: &framework::Context<'_>,
-    guild_id: i64,
-) -> Result<Option<Vec<models::AppListing>>> {
-    let repo = &ctx.data().repo;
-    let mut listings = repo.junction.get_app_listings(guild_id).await?;
-
-    if listings.is_empty() {
-        ctx.say("No apps currently being tracked.").await?;
-        Ok(None)
-    } else {
-        listings.sort_unstable_by(|a, b| a.app_name.cmp(&b.app_name));
-        Ok(Some(listings))
-    }
-}
-
 async fn get_guild_sale_threshold(ctx: &framework::Context<'_>, guild_id: i64) -> Result<i32> {
     let repo = &ctx.data().repo.discord;
     let models::Discord { sale_threshold, .. } = repo
@@ -52,86 +88,350 @@ async fn get_guild_sale_threshold(ctx: &framework::Context<'_>, guild_id: i64) -
     Ok(sale_threshold)
 }
 
-async fn paginate(
-    ctx: &framework::Context<'_>,
-    pages: &[&[models::AppListing]],
-    threshold: i32,
-) -> Result<()> {
-    let id = ctx.id().to_string();
-    let prev_button_id = format!("{}prev", id);
-    let next_button_id = format!("{}next", id);
-
-    // Send first page
-    let reply = {
-        let components = serenity::CreateActionRow::Buttons(vec![
-            serenity::CreateButton::new(&prev_button_id).emoji('◀'),
-            serenity::CreateButton::new(&next_button_id).emoji('▶'),
-        ]);
+/// Custom ids of the components on the message.
+struct ComponentIds {
+    prev: String,
+    next: String,
+    apps: String,
+    sort: String,
+    filter: String,
+    threshold: String,
+    remove: String,
+}
+
+impl ComponentIds {
+    fn new(id: &str) -> Self {
+        Self {
+            prev: format!("{id}prev"),
+            next: format!("{id}next"),
+            apps: format!("{id}apps"),
+            sort: format!("{id}sort"),
+            filter: format!("{id}filter"),
+            threshold: format!("{id}threshold"),
+            remove: format!("{id}remove"),
+        }
+    }
+}
+
+struct ListState {
+    listings: Vec<models::AppListing>,
+    guild_threshold: i32,
+    sort: ListSort,
+    show_release_dates: bool,
+    /// Lowercased query that listings must match to be shown.
+    filter: Option<String>,
+    page: usize,
+    /// App ids selected on the current page.
+    selected: Vec<i32>,
+}
+
+impl ListState {
+    fn sort_listings(&mut self) {
+        // Sort by name first so ties in other orderings stay sorted by name.
+        self.listings
+            .sort_unstable_by(|a, b| a.app_name.cmp(&b.app_name));
+        match self.sort {
+            ListSort::Name => {}
+            ListSort::AppId => self.listings.sort_by_key(|x| x.app_id),
+            ListSort::Threshold => {
+                let guild_threshold = self.guild_threshold;
+                self.listings
+                    .sort_by_key(|x| x.sale_threshold.unwrap_or(guild_threshold));
+            }
+            ListSort::Discount => self
+                .listings
+                .sort_by_key(|x| std::cmp::Reverse(x.discount_percent)),
+            ListSort::DateAdded => self.listings.sort_by_key(|x| std::cmp::Reverse(x.added_at)),
+            // Soonest concrete dates first, then vague dates, then released apps.
+            ListSort::ReleaseDate => {
+                self.listings
+                    .sort_by_key(|x| match x.upcoming_release_date.as_deref() {
+                        Some(date) => match steam::parse_release_date(date) {
+                            Some(date) => (0, Some(date)),
+                            None => (1, None),
+                        },
+                        None => (2, None),
+                    })
+            }
+        }
+    }
+
+    fn visible(&self) -> Vec<&models::AppListing> {
+        self.listings
+            .iter()
+            .filter(|x| match &self.filter {
+                Some(query) => {
+                    x.app_name.to_lowercase().contains(query) || x.app_id.to_string() == *query
+                }
+                None => true,
+            })
+            .collect()
+    }
+
+    fn page_count(&self) -> usize {
+        let page_size = config::get().commands.page_size;
+        self.visible().len().div_ceil(page_size).max(1)
+    }
+
+    fn current_page(&self) -> Vec<&models::AppListing> {
+        let page_size = config::get().commands.page_size;
+        self.visible()
+            .into_iter()
+            .skip(self.page * page_size)
+            .take(page_size)
+            .collect()
+    }
+
+    fn turn_page(&mut self, forward: bool) {
+        let page_count = self.page_count();
+        self.page = if forward {
+            (self.page + 1) % page_count
+        } else {
+            self.page.checked_sub(1).unwrap_or(page_count - 1)
+        };
+        self.selected.clear();
+    }
+
+    /// Resets to the first page, e.g., after the visible listings change.
+    fn reset_page(&mut self) {
+        self.page = 0;
+        self.selected.clear();
+    }
+
+    fn create_embed(&self) -> serenity::CreateEmbed {
+        let page = self.current_page();
+        let description = if page.is_empty() {
+            "No apps match the filter.".to_string()
+        } else {
+            page.iter()
+                .map(|listing| listing_line(listing, self.show_release_dates))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let mut footer = format!(
+            "General Discount Threshold: {}% • Sorted by {}",
+            self.guild_threshold, self.sort
+        );
+        if let Some(query) = &self.filter {
+            footer.push_str(&format!(" • Filter: {query}"));
+        }
+
+        serenity::CreateEmbed::new()
+            .title(format!(
+                "Tracked Apps {}/{}",
+                self.page + 1,
+                self.page_count()
+            ))
+            .description(description)
+            .footer(serenity::CreateEmbedFooter::new(footer))
+            .color(config::get().discord.brand_color)
+    }
+
+    fn create_components(&self, ids: &ComponentIds) -> Vec<serenity::CreateActionRow> {
+        let mut rows = Vec::new();
+
+        let page = self.current_page();
+        if !page.is_empty() {
+            let options = page
+                .iter()
+                .map(|listing| {
+                    let label =
+                        truncate(&format!("{} ({})", listing.app_name, listing.app_id), 100);
+                    serenity::CreateSelectMenuOption::new(label, listing.app_id.to_string())
+                        .default_selection(self.selected.contains(&listing.app_id))
+                })
+                .collect::<Vec<_>>();
+            let max_values = options.len() as u8;
+            let apps = serenity::CreateSelectMenu::new(
+                &ids.apps,
+                serenity::CreateSelectMenuKind::String { options },
+            )
+            .min_values(0)
+            .max_values(max_values)
+            .placeholder("Select apps to manage");
+            rows.push(serenity::CreateActionRow::SelectMenu(apps));
+        }
+
+        let sort_options = ListSort::iter()
+            .map(|x| {
+                serenity::CreateSelectMenuOption::new(format!("Sort by {x}"), x.to_string())
+                    .default_selection(x == self.sort)
+            })
+            .collect();
+        let sort = serenity::CreateSelectMenu::new(
+            &ids.sort,
+            serenity::CreateSelectMenuKind::String {
+                options: sort_options,
+            },
+        )
+        .min_values(1)
+        .max_values(1);
+        rows.push(serenity::CreateActionRow::SelectMenu(sort));
+
+        let nothing_selected = self.selected.is_empty();
+        rows.push(serenity::CreateActionRow::Buttons(vec![
+            serenity::CreateButton::new(&ids.prev).emoji('◀'),
+            serenity::CreateButton::new(&ids.next).emoji('▶'),
+            serenity::CreateButton::new(&ids.filter)
+                .label("Filter")
+                .style(serenity::ButtonStyle::Secondary),
+            serenity::CreateButton::new(&ids.threshold)
+                .label("Set Threshold")
+                .style(serenity::ButtonStyle::Primary)
+                .disabled(nothing_selected),
+            serenity::CreateButton::new(&ids.remove)
+                .label("Remove")
+                .style(serenity::ButtonStyle::Danger)
+                .disabled(nothing_selected),
+        ]));
+
+        rows
+    }
+
+    fn create_reply(&self, ids: &ComponentIds) -> poise::CreateReply {
         poise::CreateReply::default()
-            .embed(create_embed(0, pages, threshold))
-            .components(vec![components])
-    };
-    ctx.send(reply).await?;
+            .embed(self.create_embed())
+            .components(self.create_components(ids))
+    }
+
+    fn create_update(&self, ids: &ComponentIds) -> serenity::CreateInteractionResponse {
+        serenity::CreateInteractionResponse::UpdateMessage(
+            serenity::CreateInteractionResponseMessage::new()
+                .embed(self.create_embed())
+                .components(self.create_components(ids)),
+        )
+    }
+}
+
+async fn manage(ctx: &framework::Context<'_>, guild_id: i64, mut state: ListState) -> Result<()> {
+    let id = ctx.id().to_string();
+    let ids = ComponentIds::new(&id);
+    let repo = &ctx.data().repo.junction;
+
+    let handle = ctx.send(state.create_reply(&ids)).await?;
 
-    // Handle page turns
-    let mut current_page = 0;
     let mut listener = serenity::ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
         .filter(move |ev| ev.data.custom_id.starts_with(&id))
         .timeout(Duration::from_secs(300))
         .stream();
     while let Some(event) = listener.next().await {
         let action = &event.data.custom_id;
-        if *action == next_button_id {
-            current_page += 1;
-            if current_page >= pages.len() {
-                current_page = 0;
+
+        if *action == ids.next || *action == ids.prev {
+            state.turn_page(*action == ids.next);
+        } else if *action == ids.apps {
+            state.selected = parse_selections(&event)?
+                .iter()
+                .map(|x| x.parse())
+                .collect::<std::result::Result<_, _>>()?;
+        } else if *action == ids.sort {
+            let Some(sort) = parse_selections(&event)?.first() else {
+                bail!("Unexpected values: event: {event:?}");
+            };
+            state.sort = sort.parse()?;
+            state.sort_listings();
+            state.reset_page();
+        } else if *action == ids.remove {
+            repo.remove_junctions(guild_id, &state.selected).await?;
+            let removed = std::mem::take(&mut state.selected);
+            state.listings.retain(|x| !removed.contains(&x.app_id));
+            state.page = state.page.min(state.page_count() - 1);
+        } else if *action == ids.filter {
+            let Some(FilterModal { query }) =
+                poise::execute_modal_on_component_interaction(ctx, event, None, None).await?
+            else {
+                continue;
+            };
+            state.filter = query
+                .map(|x| x.trim().to_lowercase())
+                .filter(|x| !x.is_empty());
+            state.reset_page();
+            handle.edit(*ctx, state.create_reply(&ids)).await?;
+            continue;
+        } else if *action == ids.threshold {
+            let Some(modal) = poise::execute_modal_on_component_interaction::<ThresholdModal>(
+                ctx, event, None, None,
+            )
+            .await?
+            else {
+                continue;
+            };
+            let Some(threshold) = modal.parse_threshold() else {
+                let reply = poise::CreateReply::default()
+                    .content("Threshold must be a number from 1 to 99.")
+                    .ephemeral(true);
+                ctx.send(reply).await?;
+                continue;
+            };
+
+            let failed = repo
+                .set_thresholds(guild_id, threshold, state.selected.clone())
+                .await;
+            for listing in &mut state.listings {
+                if state.selected.contains(&listing.app_id) && !failed.contains(&listing.app_id) {
+                    listing.sale_threshold = Some(threshold);
+                }
+            }
+            if !failed.is_empty() {
+                let reply = poise::CreateReply::default()
+                    .content("Failed to update some thresholds. Please try again.")
+                    .ephemeral(true);
+                ctx.send(reply).await?;
             }
-        } else if *action == prev_button_id {
-            current_page = current_page.checked_sub(1).unwrap_or(pages.len() - 1);
+            state.sort_listings();
+            state.reset_page();
+            handle.edit(*ctx, state.create_reply(&ids)).await?;
+            continue;
         } else {
             continue;
         }
 
-        let update = serenity::CreateInteractionResponse::UpdateMessage(
-            serenity::CreateInteractionResponseMessage::new().embed(create_embed(
-                current_page,
-                pages,
-                threshold,
-            )),
-        );
-        event.create_response(&ctx, update).await?;
+        event
+            .create_response(&ctx, state.create_update(&ids))
+            .await?;
     }
 
     Ok(())
 }
 
-fn create_embed(
-    current_page: usize,
-    pages: &[&[models::AppListing]],
-    threshold: i32,
-) -> serenity::CreateEmbed {
-    let description = pages[current_page]
-        .iter()
-        .map(
-            |models::AppListing {
-                 app_id,
-                 app_name,
-                 sale_threshold,
-             }| {
-                match sale_threshold {
-                    Some(threshold) => format!("{app_name} ({app_id}) ({threshold}%)"),
-                    None => format!("{app_name} ({app_id})"),
-                }
-            },
-        )
-        .collect::<Vec<_>>()
-        .join("\n");
+fn parse_selections(event: &serenity::ComponentInteraction) -> Result<&[String]> {
+    let serenity::ComponentInteractionDataKind::StringSelect { values } = &event.data.kind else {
+        bail!("Not StringSelect. event: {event:?}");
+    };
+    Ok(values)
+}
+
+fn listing_line(listing: &models::AppListing, show_release_date: bool) -> String {
+    let models::AppListing {
+        app_id,
+        app_name,
+        sale_threshold,
+        discount_percent,
+        upcoming_release_date,
+        ..
+    } = listing;
 
-    let footer = format!("General Discount Threshold: {threshold}%");
+    let mut line = match sale_threshold {
+        Some(threshold) => format!("{app_name} ({app_id}) ({threshold}%)"),
+        None => format!("{app_name} ({app_id})"),
+    };
+    if let Some(discount) = discount_percent.filter(|&d| d > 0) {
+        line.push_str(&format!(" **-{discount}%**"));
+    }
+    if show_release_date && let Some(date) = upcoming_release_date {
+        line.push_str(&format!(" • Releases {date}"));
+    }
+    line
+}
 
-    serenity::CreateEmbed::new()
-        .title(format!("Tracked Apps {}/{}", current_page + 1, pages.len()))
-        .description(description)
-        .footer(serenity::CreateEmbedFooter::new(footer))
-        .color(config::BRAND_DARK_COLOR)
+/// Truncates `s` to at most `max_chars` characters, marking truncation with an ellipsis.
+fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return s.to_string();
+    }
+    let mut truncated = s.chars().take(max_chars - 1).collect::<String>();
+    truncated.push('…');
+    truncated
 }
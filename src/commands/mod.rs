@@ -1,5 +1,8 @@
 //! This module provides Discord command handlers.
 
+mod modals;
+mod paginate;
+
 mod help;
 pub use help::*;
 
@@ -21,5 +24,35 @@ pub use remove_apps::*;
 mod add_apps;
 pub use add_apps::*;
 
+mod add_dlc;
+pub use add_dlc::*;
+
 mod search;
 pub use search::*;
+
+mod deals;
+pub use deals::*;
+
+mod app_info;
+pub use app_info::*;
+
+mod set_delivery_mode;
+pub use set_delivery_mode::*;
+
+mod set_timezone;
+pub use set_timezone::*;
+
+mod set_quiet_hours;
+pub use set_quiet_hours::*;
+
+mod set_sale_end_reminder;
+pub use set_sale_end_reminder::*;
+
+mod set_target_price;
+pub use set_target_price::*;
+
+mod set_alert_options;
+pub use set_alert_options::*;
+
+mod owner;
+pub use owner::*;
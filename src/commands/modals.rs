@@ -0,0 +1,20 @@
+#[derive(Debug, poise::Modal)]
+#[name = "Set Discount Threshold"]
+pub struct ThresholdModal {
+    #[name = "Discount threshold (1-99)"]
+    #[placeholder = "25"]
+    #[min_length = 1]
+    #[max_length = 2]
+    pub threshold: String,
+}
+
+impl ThresholdModal {
+    /// Parses the submitted threshold, returning `None` if it's not within 1-99.
+    pub fn parse_threshold(&self) -> Option<i32> {
+        self.threshold
+            .trim()
+            .parse()
+            .ok()
+            .filter(|x| (1..=99).contains(x))
+    }
+}
@@ -0,0 +1,339 @@
+use std::fmt::Write;
+
+use futures::TryStreamExt;
+use poise::serenity_prelude as serenity;
+use tracing::{error, info};
+
+use crate::{Result, alerts, events, framework, health, lease, scheduler, shutdown};
+
+const TOP_APPS_LIMIT: i64 = 10;
+/// Discord's message length limit.
+const MAX_MESSAGE_LEN: usize = 2000;
+
+/// Operator commands for the bot owners.
+#[poise::command(
+    slash_command,
+    owners_only,
+    hide_in_help,
+    // Hides the command from most members of guilds it's registered in.
+    default_member_permissions = "ADMINISTRATOR",
+    subcommands(
+        "check_now",
+        "dry_run",
+        "jobs",
+        "run_job",
+        "check_app",
+        "status",
+        "stats",
+        "remove_orphans",
+        "broadcast"
+    ),
+    subcommand_required
+)]
+pub async fn owner(_ctx: framework::Context<'_>) -> Result<()> {
+    Ok(())
+}
+
+/// Start checking all apps now.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn check_now(ctx: framework::Context<'_>) -> Result<()> {
+    if events::is_checking() {
+        reply(&ctx, "A check is already running.").await?;
+        return Ok(());
+    }
+
+    trigger_job(&ctx, scheduler::CHECK_APPS, "Started checking apps.").await
+}
+
+/// List the scheduled jobs and their last runs.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn jobs(ctx: framework::Context<'_>) -> Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let runs = ctx
+        .data()
+        .repo
+        .jobs
+        .get_runs()
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    let mut content = String::new();
+    for job in scheduler::SCHEDULER.jobs() {
+        let last_run = match runs.iter().find(|run| run.job == job.name) {
+            Some(run) => {
+                let mut last_run = format!(
+                    "{} <t:{}:R> ({}, {})",
+                    run.status,
+                    run.started_at.timestamp_millis() / 1000,
+                    run.trigger,
+                    run.holder,
+                );
+                if let Some(err) = &run.error {
+                    write!(last_run, ": {err}")?;
+                }
+                last_run
+            }
+            None => "Never".to_string(),
+        };
+        writeln!(
+            content,
+            "**{}**: {}\n\
+            Schedule: {}, next <t:{}:R>. Jitter: {}s, timeout: {}s, overlap: {}\n\
+            Running: {}. Last run: {last_run}",
+            job.name,
+            job.description,
+            job.schedule,
+            job.next_run_at().timestamp(),
+            job.jitter.as_secs(),
+            job.timeout.as_secs(),
+            job.overlap,
+            job.is_running(),
+        )?;
+    }
+    reply(&ctx, content).await?;
+
+    Ok(())
+}
+
+/// Run a scheduled job now.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn run_job(
+    ctx: framework::Context<'_>,
+    #[autocomplete = "autocomplete_job"]
+    #[description = "Name of the job"]
+    job: String,
+) -> Result<()> {
+    trigger_job(&ctx, &job, format!("Started {job}.")).await
+}
+
+async fn autocomplete_job(_ctx: framework::Context<'_>, partial: &str) -> Vec<String> {
+    scheduler::SCHEDULER
+        .jobs()
+        .iter()
+        .map(|job| job.name)
+        .filter(|name| name.contains(partial))
+        .map(str::to_string)
+        .collect()
+}
+
+async fn trigger_job(
+    ctx: &framework::Context<'_>,
+    job: &str,
+    started: impl Into<String>,
+) -> Result<()> {
+    info!(job, "Job triggered by owner");
+    match scheduler::SCHEDULER.trigger(job, ctx.data().clone()) {
+        Ok(()) => reply(ctx, started).await?,
+        Err(err) => reply(ctx, format!("{err}.")).await?,
+    }
+
+    Ok(())
+}
+
+/// Check all apps without updating anything or sending alerts, then post a report.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn dry_run(ctx: framework::Context<'_>) -> Result<()> {
+    if events::is_checking() {
+        reply(&ctx, "A check is already running.").await?;
+        return Ok(());
+    }
+
+    // Checks can outlast the interaction, so the report is posted separately.
+    let data = ctx.data().clone();
+    let channel = ctx.channel_id();
+    tokio::spawn(async move {
+        info!("Dry run triggered by owner");
+        let message = match events::run_check(&data, true, &shutdown::SHUTDOWN).await {
+            Some(Ok(alerts)) => serenity::CreateMessage::new()
+                .content("Dry run finished.")
+                .add_file(serenity::CreateAttachment::bytes(
+                    alerts::report(&alerts),
+                    "dry_run_report.txt",
+                )),
+            Some(Err(_)) => serenity::CreateMessage::new().content("Dry run failed."),
+            None => serenity::CreateMessage::new().content("A check was already running."),
+        };
+        channel
+            .send_message(&data.http, message)
+            .await
+            .inspect_err(|err| error!(?err, "Failed to send dry run report"))
+            .ok();
+    });
+    reply(&ctx, "Started a dry run. The report will be posted here.").await?;
+
+    Ok(())
+}
+
+/// Check an app without updating anything or sending alerts.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn check_app(
+    ctx: framework::Context<'_>,
+    #[description = "App ID to check"] app_id: i32,
+) -> Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let Some(alerts) = events::dry_run_app(ctx.data(), app_id).await? else {
+        reply(&ctx, format!("App {app_id} wasn't found on Steam.")).await?;
+        return Ok(());
+    };
+    if alerts.is_empty() {
+        reply(
+            &ctx,
+            format!("No guild would be alerted about app {app_id}."),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let content = format!(
+        "Alerts that would be sent for app {app_id}:\n{}",
+        alerts::report(&alerts)
+    );
+    reply(&ctx, content).await?;
+
+    Ok(())
+}
+
+/// Show the status of the check loop.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn status(ctx: framework::Context<'_>) -> Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let report = health::HEALTH.report().await;
+    let last_check = match health::HEALTH.last_check() {
+        Some(at) => format!("<t:{}:R>", at.timestamp()),
+        None => "Never".to_string(),
+    };
+    let lease_holder = match lease::LEASE.current_holder(&ctx.data().repo).await? {
+        Some(holder) if holder == lease::LEASE.holder() => format!("{holder} (this process)"),
+        Some(holder) => holder,
+        None => "None".to_string(),
+    };
+    let content = format!(
+        "Lease holder: {lease_holder}\n\
+        Checking: {}\n\
+        Last successful check: {last_check}\n\
+        Next check: <t:{}:R>\n\
+        Gateway connected: {}\n\
+        Database reachable: {}",
+        events::is_checking(),
+        scheduler::SCHEDULER
+            .job(scheduler::CHECK_APPS)
+            .expect("check apps job should be registered")
+            .next_run_at()
+            .timestamp(),
+        report.gateway_connected,
+        report.database_reachable,
+    );
+    reply(&ctx, content).await?;
+
+    Ok(())
+}
+
+/// Show the guild count and most tracked apps.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn stats(ctx: framework::Context<'_>) -> Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let top_apps = ctx
+        .data()
+        .repo
+        .junction
+        .get_most_tracked_apps(TOP_APPS_LIMIT)
+        .await?;
+
+    let mut content = format!("Guilds: {}\n", ctx.cache().guild_count());
+    content.push_str("Most tracked apps:\n");
+    for app in top_apps {
+        writeln!(
+            content,
+            "- {} ({}): {} guilds",
+            app.app_name, app.app_id, app.trackers
+        )?;
+    }
+    reply(&ctx, content).await?;
+
+    Ok(())
+}
+
+/// Delete apps no guild tracks.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn remove_orphans(ctx: framework::Context<'_>) -> Result<()> {
+    trigger_job(
+        &ctx,
+        scheduler::REMOVE_ORPHANS,
+        "Started removing orphaned apps.",
+    )
+    .await
+}
+
+/// Send an announcement to every guild's bound channel.
+#[poise::command(slash_command, owners_only)]
+#[tracing::instrument(level = "error", skip(ctx))]
+async fn broadcast(
+    ctx: framework::Context<'_>,
+    #[description = "Announcement to send"] message: String,
+) -> Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let mut sent = 0;
+    let mut failed = 0;
+    let mut guilds = ctx.data().repo.discord.get_bound_guilds().await?;
+    while let Some(discord) = guilds.try_next().await? {
+        let Ok(channel_id) = discord.channel_id.try_into() else {
+            error!(
+                guild_id = discord.server_id,
+                channel_id = discord.channel_id,
+                "Failed to broadcast to invalid channel"
+            );
+            failed += 1;
+            continue;
+        };
+        let res = serenity::ChannelId::new(channel_id)
+            .send_message(ctx.http(), serenity::CreateMessage::new().content(&message))
+            .await;
+        match res {
+            Ok(_) => sent += 1,
+            Err(err) => {
+                error!(?err, guild_id = discord.server_id, "Failed to broadcast");
+                failed += 1;
+            }
+        }
+    }
+    reply(
+        &ctx,
+        format!("Broadcast sent to {sent} guilds. Failed for {failed} guilds."),
+    )
+    .await?;
+
+    Ok(())
+}
+
+async fn reply(ctx: &framework::Context<'_>, content: impl Into<String>) -> Result<()> {
+    let mut content = content.into();
+    if content.len() > MAX_MESSAGE_LEN {
+        let mut end = MAX_MESSAGE_LEN - 3;
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        content.truncate(end);
+        content.push_str("...");
+    }
+    ctx.send(
+        poise::CreateReply::default()
+            .content(content)
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use poise::serenity_prelude as serenity;
+
+use crate::{Result, framework};
+
+/// Sends the first page and then lets users flip through `page_count` pages
+/// with prev/next buttons until the listener times out.
+pub async fn paginate(
+    ctx: &framework::Context<'_>,
+    page_count: usize,
+    create_page: impl Fn(usize) -> serenity::CreateEmbed,
+) -> Result<()> {
+    let id = ctx.id().to_string();
+    let prev_button_id = format!("{}prev", id);
+    let next_button_id = format!("{}next", id);
+
+    // Send first page
+    let reply = {
+        let components = serenity::CreateActionRow::Buttons(vec![
+            serenity::CreateButton::new(&prev_button_id).emoji('◀'),
+            serenity::CreateButton::new(&next_button_id).emoji('▶'),
+        ]);
+        poise::CreateReply::default()
+            .embed(create_page(0))
+            .components(vec![components])
+    };
+    ctx.send(reply).await?;
+
+    // Handle page turns
+    let mut current_page = 0;
+    let mut listener = serenity::ComponentInteractionCollector::new(ctx)
+        .filter(move |ev| ev.data.custom_id.starts_with(&id))
+        .timeout(Duration::from_secs(300))
+        .stream();
+    while let Some(event) = listener.next().await {
+        let action = &event.data.custom_id;
+        if *action == next_button_id {
+            current_page += 1;
+            if current_page >= page_count {
+                current_page = 0;
+            }
+        } else if *action == prev_button_id {
+            current_page = current_page.checked_sub(1).unwrap_or(page_count - 1);
+        } else {
+            continue;
+        }
+
+        let update = serenity::CreateInteractionResponse::UpdateMessage(
+            serenity::CreateInteractionResponseMessage::new().embed(create_page(current_page)),
+        );
+        event.create_response(&ctx, update).await?;
+    }
+
+    Ok(())
+}
@@ -47,7 +47,7 @@ fn search_result_dropdown(id: &str, results: &[steam::SearchResult]) -> poise::C
             "Results may include non-addable apps. \
 Make sure your choice is either priced or yet to be released.",
         ))
-        .color(config::BRAND_DARK_COLOR);
+        .color(config::get().discord.brand_color);
 
     let mut options = results
         .iter()
@@ -170,6 +170,7 @@ async fn add_app_to_db(
         is_trailing_sale_day: false,
         coming_soon: app.release_date.coming_soon,
         sale_threshold: None,
+        ..Default::default()
     };
     repo.junction
         .add_junction_if_not_exists(&junction)
@@ -189,7 +190,7 @@ fn create_edit(description: impl Into<String>) -> serenity::EditInteractionRespo
     let embed = serenity::CreateEmbed::new()
         .title("Search App")
         .description(description.into())
-        .color(config::BRAND_DARK_COLOR);
+        .color(config::get().discord.brand_color);
 
     serenity::EditInteractionResponse::new()
         .embed(embed)
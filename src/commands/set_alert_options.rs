@@ -0,0 +1,68 @@
+use anyhow::Context;
+
+use crate::{Result, framework};
+
+/// Choose which optional alerts are sent. Omitted options are left unchanged.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn set_alert_options(
+    ctx: framework::Context<'_>,
+    #[description = "Alert when a tracked app's base price increases or decreases"]
+    price_changes: Option<bool>,
+    #[description = "Untrack apps once they're alerted as delisted from the store"]
+    auto_untrack_delisted: Option<bool>,
+    #[min = 0]
+    #[max = 100]
+    #[description = "Only alert sales of apps with at least this % of positive reviews. 0 to disable"]
+    min_review_percent: Option<i32>,
+    #[description = "Only alert sales of apps that are Steam Deck Verified or Playable"]
+    require_deck_playable: Option<bool>,
+    #[description = "Announce the start of Steam's seasonal sales and Next Fest"]
+    sale_events: Option<bool>,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+    let repo = &ctx.data().repo.discord;
+
+    let mut changes = Vec::new();
+    if let Some(enabled) = price_changes {
+        repo.set_price_change_alerts(guild_id, enabled).await?;
+        changes.push(format!("Price change alerts: {}", on_off(enabled)));
+    }
+    if let Some(enabled) = auto_untrack_delisted {
+        repo.set_auto_untrack_delisted(guild_id, enabled).await?;
+        changes.push(format!("Auto-untrack delisted apps: {}", on_off(enabled)));
+    }
+    if let Some(percent) = min_review_percent {
+        let percent = Some(percent).filter(|&x| x > 0);
+        repo.set_min_review_percent(guild_id, percent).await?;
+        changes.push(match percent {
+            Some(percent) => format!("Minimum positive reviews for sale alerts: {percent}%"),
+            None => "Minimum positive reviews for sale alerts: off".to_string(),
+        });
+    }
+    if let Some(required) = require_deck_playable {
+        repo.set_require_deck_playable(guild_id, required).await?;
+        changes.push(format!(
+            "Require Steam Deck Verified or Playable for sale alerts: {}",
+            on_off(required)
+        ));
+    }
+    if let Some(enabled) = sale_events {
+        repo.set_sale_event_announcements(guild_id, enabled).await?;
+        changes.push(format!("Sale event announcements: {}", on_off(enabled)));
+    }
+
+    if changes.is_empty() {
+        ctx.say("No options were provided.").await?;
+    } else {
+        ctx.say(changes.join("\n")).await?;
+    }
+
+    Ok(())
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
@@ -0,0 +1,101 @@
+use anyhow::Context;
+
+use crate::{Result, framework, models};
+
+const DEFAULT_AUTO_THRESHOLD: i32 = 5;
+
+const DEFAULT_WEEKLY_HOUR: u32 = 17;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum DeliveryModeChoice {
+    Individual,
+    Digest,
+    Auto,
+    Weekly,
+}
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum WeekdayChoice {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl From<WeekdayChoice> for chrono::Weekday {
+    fn from(weekday: WeekdayChoice) -> Self {
+        match weekday {
+            WeekdayChoice::Monday => chrono::Weekday::Mon,
+            WeekdayChoice::Tuesday => chrono::Weekday::Tue,
+            WeekdayChoice::Wednesday => chrono::Weekday::Wed,
+            WeekdayChoice::Thursday => chrono::Weekday::Thu,
+            WeekdayChoice::Friday => chrono::Weekday::Fri,
+            WeekdayChoice::Saturday => chrono::Weekday::Sat,
+            WeekdayChoice::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+/// Set whether alerts are sent one by one or grouped into a digest.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn set_delivery_mode(
+    ctx: framework::Context<'_>,
+    mode: DeliveryModeChoice,
+    #[min = 1]
+    #[max = 100]
+    #[description = "With auto, send a digest when a check has more alerts than this"]
+    threshold: Option<i32>,
+    #[description = "With weekly, the day to send the digest on"] weekday: Option<WeekdayChoice>,
+    #[min = 0]
+    #[max = 23]
+    #[description = "With weekly, the hour to send the digest at, in the server's timezone"]
+    hour: Option<u32>,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let (mode, description) = match mode {
+        DeliveryModeChoice::Individual => (
+            models::DeliveryMode::Individual,
+            "Alerts will be sent individually.".to_string(),
+        ),
+        DeliveryModeChoice::Digest => (
+            models::DeliveryMode::Digest,
+            "Alerts will be grouped into a digest.".to_string(),
+        ),
+        DeliveryModeChoice::Auto => {
+            let threshold = threshold.unwrap_or(DEFAULT_AUTO_THRESHOLD);
+            (
+                models::DeliveryMode::Auto { threshold },
+                format!(
+                    "Alerts will be grouped into a digest when there are more than {threshold}."
+                ),
+            )
+        }
+        DeliveryModeChoice::Weekly => {
+            let weekday = weekday.unwrap_or(WeekdayChoice::Monday);
+            let hour = hour.unwrap_or(DEFAULT_WEEKLY_HOUR);
+            (
+                models::DeliveryMode::Weekly {
+                    weekday: weekday.into(),
+                    hour,
+                },
+                format!(
+                    "Instead of alerts, a weekly digest will be sent every {weekday:?} at {hour:02}:00 \
+                    in the server's timezone. Use `/set_timezone` to change the timezone.",
+                ),
+            )
+        }
+    };
+
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+    let repo = &ctx.data().repo.discord;
+    repo.set_delivery_mode(guild_id, mode).await?;
+
+    ctx.say(description).await?;
+
+    Ok(())
+}
@@ -57,7 +57,7 @@ pub async fn set_discount_threshold(
                 .title("Set Discount Threshold Failed On")
                 .description(description)
                 .footer(serenity::CreateEmbedFooter::new(footer))
-                .color(config::BRAND_DARK_COLOR)
+                .color(config::get().discord.brand_color)
                 .to_reply();
             ctx.send(reply).await?;
         }
@@ -0,0 +1,45 @@
+use anyhow::Context;
+
+use crate::{Result, framework, models};
+
+/// Set hours during which alerts are held back and sent once they end.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn set_quiet_hours(
+    ctx: framework::Context<'_>,
+    #[min = 0]
+    #[max = 23]
+    #[description = "Hour quiet hours start at, in the server's timezone. Omit both to disable"]
+    start: Option<u32>,
+    #[min = 0]
+    #[max = 23]
+    #[description = "Hour quiet hours end at, in the server's timezone. Omit both to disable"]
+    end: Option<u32>,
+) -> Result<()> {
+    let quiet_hours = match (start, end) {
+        (Some(start), Some(end)) if start != end => Some(models::QuietHours { start, end }),
+        (None, None) => None,
+        _ => {
+            ctx.say("Please provide two different hours for the start and end, or neither to disable quiet hours.")
+                .await?;
+            return Ok(());
+        }
+    };
+    ctx.defer().await?;
+
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+    let repo = &ctx.data().repo.discord;
+    repo.set_quiet_hours(guild_id, quiet_hours).await?;
+
+    let description = match quiet_hours {
+        Some(q) => format!(
+            "Alerts between {:02}:00 and {:02}:00 in the server's timezone will be sent once quiet hours end. \
+            Use `/set_timezone` to change the timezone.",
+            q.start, q.end
+        ),
+        None => "Quiet hours disabled.".to_string(),
+    };
+    ctx.say(description).await?;
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anyhow::Context;
+
+use crate::{Result, framework};
+
+/// Get a last chance reminder before an alerted sale ends.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn set_sale_end_reminder(
+    ctx: framework::Context<'_>,
+    #[min = 1]
+    #[max = 72]
+    #[description = "How many hours before a sale ends to send the reminder. Omit to disable"]
+    hours: Option<i32>,
+) -> Result<()> {
+    ctx.defer().await?;
+
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+    let repo = &ctx.data().repo.discord;
+    repo.set_sale_end_reminder_hours(guild_id, hours).await?;
+
+    let description = match hours {
+        Some(hours) => format!(
+            "A reminder will be sent {hours} hours before an alerted sale ends, \
+            for sales where Steam provides an end date."
+        ),
+        None => "Sale end reminders disabled.".to_string(),
+    };
+    ctx.say(description).await?;
+
+    Ok(())
+}
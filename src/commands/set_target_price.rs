@@ -0,0 +1,53 @@
+use anyhow::Context;
+
+use crate::{Result, framework, util};
+
+/// Sets the price at or below which a sale alert is triggered, regardless of discount.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn set_target_price(
+    ctx: framework::Context<'_>,
+    #[max_length = 150]
+    #[rename = "appids"]
+    #[description = "Use this target price for these specific appids"]
+    app_ids: String,
+    #[max_length = 20]
+    #[description = "Price without the currency symbol, e.g. 19.99. Omit to remove the target price"]
+    price: Option<String>,
+) -> Result<()> {
+    let Ok(app_ids) = util::parse_csv_app_ids(&app_ids) else {
+        ctx.say(util::PARSE_APP_IDS_FAIL_MSG).await?;
+        return Ok(());
+    };
+    let target_price = match price.as_deref().map(util::parse_price) {
+        Some(Some(price)) => Some(price),
+        Some(None) => {
+            ctx.say(util::PARSE_PRICE_FAIL_MSG).await?;
+            return Ok(());
+        }
+        None => None,
+    };
+    ctx.defer().await?;
+
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+    let repo = &ctx.data().repo.junction;
+    let result = repo
+        .set_target_prices(guild_id, target_price, &app_ids)
+        .await?;
+
+    let action = match target_price {
+        Some(price) => format!("Set target price to {}", util::format_price(price)),
+        None => "Removed target price".to_string(),
+    };
+    let mut description = format!(
+        "{action} for {} of {} apps.",
+        result.matched_count,
+        app_ids.len()
+    );
+    if result.matched_count < app_ids.len() as u64 {
+        description.push_str(" Please double check the rest are valid, tracked appids.");
+    }
+    ctx.say(description).await?;
+
+    Ok(())
+}
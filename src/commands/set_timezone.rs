@@ -0,0 +1,42 @@
+use anyhow::Context;
+
+use crate::{Result, framework};
+
+const MAX_SUGGESTIONS: usize = 25;
+
+/// Set the server's timezone used for scheduled deliveries and quiet hours.
+#[poise::command(slash_command, user_cooldown = 3)]
+#[tracing::instrument(level = "error", skip(ctx))]
+pub async fn set_timezone(
+    ctx: framework::Context<'_>,
+    #[autocomplete = "autocomplete_timezone"]
+    #[description = "IANA timezone, e.g. America/New_York or Asia/Tokyo"]
+    timezone: String,
+) -> Result<()> {
+    let Ok(timezone) = timezone.parse::<chrono_tz::Tz>() else {
+        ctx.say("Unknown timezone. Please pick one of the suggested timezones.")
+            .await?;
+        return Ok(());
+    };
+    ctx.defer().await?;
+
+    let guild_id: i64 = ctx.guild_id().with_context(|| "Getting guild_id")?.into();
+    let repo = &ctx.data().repo.discord;
+    repo.set_timezone(guild_id, timezone).await?;
+
+    ctx.say(format!("Timezone set to {}", timezone.name()))
+        .await?;
+
+    Ok(())
+}
+
+async fn autocomplete_timezone(_ctx: framework::Context<'_>, partial: &str) -> Vec<String> {
+    let partial = partial.to_lowercase();
+    chrono_tz::TZ_VARIANTS
+        .iter()
+        .map(|tz| tz.name())
+        .filter(|name| name.to_lowercase().contains(&partial))
+        .take(MAX_SUGGESTIONS)
+        .map(str::to_string)
+        .collect()
+}
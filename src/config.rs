@@ -1,6 +1,646 @@
-//! This module provides general configurations.
+//! This module provides the bot's [`Config`], loaded at startup from a TOML
+//! file with environment variable overrides.
+
+use std::{net::SocketAddr, str::FromStr, sync::OnceLock};
 
 use poise::serenity_prelude as serenity;
+use serde::Deserialize;
+
+use crate::{StdResult, telemetry, util};
+
+/// Path of the config file if `CONFIG_FILE` isn't set.
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
 
 /// rgb(107, 76, 136)
-pub const BRAND_DARK_COLOR: serenity::Color = serenity::Color::new(0x6B4C88);
+const DEFAULT_BRAND_COLOR: serenity::Color = serenity::Color::new(0x6B4C88);
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Error variants when loading the [`Config`].
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    #[error("Failed to read config file {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse config file {path}: {source}")]
+    Parse {
+        path: String,
+        source: toml::de::Error,
+    },
+    #[error(transparent)]
+    Env(#[from] util::EnvVarError),
+    #[error("Invalid config value for {field}: {reason}")]
+    Invalid { field: &'static str, reason: String },
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub discord: DiscordConfig,
+    pub mongodb: MongoConfig,
+    pub steam: SteamConfig,
+    pub check: CheckConfig,
+    pub commands: CommandsConfig,
+    pub http: HttpConfig,
+    pub log: LogConfig,
+    pub shutdown: ShutdownConfig,
+    pub lease: LeaseConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscordConfig {
+    pub token: String,
+    /// Registers commands to this guild instead of globally.
+    pub dev_guild_id: Option<u64>,
+    /// Users allowed to use owner commands besides the application owner.
+    pub owner_ids: Vec<u64>,
+    /// Registers owner commands to this guild instead of globally. Ignored if
+    /// `dev_guild_id` is set.
+    pub owner_guild_id: Option<u64>,
+    /// Color of embeds, e.g. "#6B4C88".
+    #[serde(deserialize_with = "deserialize_color")]
+    pub brand_color: serenity::Color,
+    /// Which gateway shards this process runs.
+    pub sharding: Sharding,
+}
+
+/// Which gateway shards a process runs. Scheduled work runs in whichever process
+/// holds the scheduler lease, regardless of its shards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Sharding {
+    /// A single shard, enough for bots in fewer than 2500 guilds.
+    #[default]
+    Single,
+    /// As many shards as Discord recommends, all in this process.
+    Auto,
+    /// Shards `first` to `last` inclusive out of `total`, for splitting shards
+    /// across processes.
+    Range { first: u32, last: u32, total: u32 },
+}
+
+impl FromStr for Sharding {
+    type Err = String;
+
+    /// Parses "single", "auto" or a range like "0-3/8".
+    fn from_str(s: &str) -> StdResult<Self, Self::Err> {
+        match s.trim() {
+            "single" => Ok(Sharding::Single),
+            "auto" => Ok(Sharding::Auto),
+            range => {
+                let parse = || -> Option<Self> {
+                    let (shards, total) = range.split_once('/')?;
+                    let (first, last) = shards.split_once('-')?;
+                    Some(Sharding::Range {
+                        first: first.trim().parse().ok()?,
+                        last: last.trim().parse().ok()?,
+                        total: total.trim().parse().ok()?,
+                    })
+                };
+                parse()
+                    .ok_or_else(|| format!("{s} is not single, auto or a shard range like 0-3/8"))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MongoConfig {
+    pub uri: String,
+    pub dbname: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SteamConfig {
+    /// Base url for the store endpoint.
+    pub store_url: String,
+    /// Base url for the community endpoint.
+    pub community_url: String,
+    /// Max concurrent requests when fetching apps in bulk.
+    pub fetch_buffer_size: usize,
+    /// Max retries of an app fetch during a check after being rate-limited.
+    pub max_tries: u32,
+    /// Seconds to back off after being rate-limited during a check.
+    pub retry_timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CheckConfig {
+    /// UTC hour of the daily check.
+    pub hour: u32,
+    /// Minute of the hour of the daily check.
+    pub minute: u32,
+    /// Whether scheduled jobs only log what they would send, without writing or sending.
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommandsConfig {
+    /// Apps listed per page by paginated commands, up to 25.
+    pub page_size: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpConfig {
+    /// Address to serve /metrics, /healthz and /readyz on. Empty disables the server.
+    pub addr: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LogConfig {
+    pub format: telemetry::LogFormat,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShutdownConfig {
+    /// Seconds to wait for in-flight work and connections to close on shutdown.
+    pub timeout_secs: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LeaseConfig {
+    /// Seconds until the scheduled jobs lease expires unless renewed. A standby
+    /// process takes over at most this long after the holder dies.
+    pub ttl_secs: u64,
+}
+
+impl Default for DiscordConfig {
+    fn default() -> Self {
+        Self {
+            token: String::new(),
+            dev_guild_id: None,
+            owner_ids: Vec::new(),
+            owner_guild_id: None,
+            brand_color: DEFAULT_BRAND_COLOR,
+            sharding: Sharding::default(),
+        }
+    }
+}
+
+impl Default for SteamConfig {
+    fn default() -> Self {
+        Self {
+            store_url: "https://store.steampowered.com".to_string(),
+            community_url: "https://steamcommunity.com".to_string(),
+            fetch_buffer_size: 5,
+            max_tries: 5,
+            retry_timeout_secs: 300,
+        }
+    }
+}
+
+impl Default for CheckConfig {
+    fn default() -> Self {
+        Self {
+            hour: 17,
+            minute: 0,
+            dry_run: false,
+        }
+    }
+}
+
+impl Default for CommandsConfig {
+    fn default() -> Self {
+        Self { page_size: 10 }
+    }
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            addr: "0.0.0.0:8080".to_string(),
+        }
+    }
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self { timeout_secs: 30 }
+    }
+}
+
+impl Default for LeaseConfig {
+    fn default() -> Self {
+        Self { ttl_secs: 30 }
+    }
+}
+
+impl Config {
+    /// Loads the config file at `CONFIG_FILE`, or `config.toml` if it exists,
+    /// then applies environment variable overrides and validates the result.
+    pub fn load() -> StdResult<Self, ConfigError> {
+        let (path, required) = match util::env_var::<String>("CONFIG_FILE") {
+            Ok(x) => (x, true),
+            Err(util::EnvVarError::InvalidOrMissingKey { .. }) => {
+                (DEFAULT_CONFIG_FILE.to_string(), false)
+            }
+            Err(err) => Err(err)?,
+        };
+
+        let mut config = match std::fs::read_to_string(&path) {
+            Ok(x) => toml::from_str(&x).map_err(|source| ConfigError::Parse {
+                path: path.clone(),
+                source,
+            })?,
+            Err(err) if !required && err.kind() == std::io::ErrorKind::NotFound => Self::default(),
+            Err(source) => return Err(ConfigError::Read { path, source }),
+        };
+        config.apply_env()?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// Overrides values with their environment variables, if set.
+    fn apply_env(&mut self) -> StdResult<(), ConfigError> {
+        let Self {
+            discord,
+            mongodb,
+            steam,
+            check,
+            commands,
+            http,
+            log,
+            shutdown,
+            lease,
+        } = self;
+
+        env_override(&mut discord.token, "DISCORD_TOKEN")?;
+        if let Some(id) = env_var("DISCORD_DEVGUILDID")? {
+            discord.dev_guild_id = Some(id);
+        }
+        if let Some(ids) = env_var::<String>("DISCORD_OWNERIDS")? {
+            discord.owner_ids = parse_list(&ids).map_err(|err: std::num::ParseIntError| {
+                util::EnvVarError::InvalidValue {
+                    key: "DISCORD_OWNERIDS".to_string(),
+                    err: err.to_string(),
+                }
+            })?;
+        }
+        if let Some(id) = env_var("DISCORD_OWNERGUILDID")? {
+            discord.owner_guild_id = Some(id);
+        }
+        if let Some(color) = env_var::<String>("DISCORD_BRANDCOLOR")? {
+            discord.brand_color =
+                parse_color(&color).map_err(|err| util::EnvVarError::InvalidValue {
+                    key: "DISCORD_BRANDCOLOR".to_string(),
+                    err,
+                })?;
+        }
+        env_override(&mut discord.sharding, "DISCORD_SHARDS")?;
+        env_override(&mut mongodb.uri, "MONGODB_URI")?;
+        env_override(&mut mongodb.dbname, "MONGODB_DBNAME")?;
+        env_override(&mut steam.store_url, "STEAM_STOREURL")?;
+        env_override(&mut steam.community_url, "STEAM_COMMUNITYURL")?;
+        env_override(&mut steam.fetch_buffer_size, "STEAM_FETCHBUFFERSIZE")?;
+        env_override(&mut steam.max_tries, "STEAM_MAXTRIES")?;
+        env_override(&mut steam.retry_timeout_secs, "STEAM_RETRYTIMEOUTSECS")?;
+        env_override(&mut check.hour, "CHECK_HOUR")?;
+        env_override(&mut check.minute, "CHECK_MINUTE")?;
+        env_override(&mut check.dry_run, "DRY_RUN")?;
+        env_override(&mut commands.page_size, "COMMANDS_PAGESIZE")?;
+        env_override(&mut http.addr, "HTTP_ADDR")?;
+        env_override(&mut log.format, "LOG_FORMAT")?;
+        env_override(&mut shutdown.timeout_secs, "SHUTDOWN_TIMEOUTSECS")?;
+        env_override(&mut lease.ttl_secs, "LEASE_TTLSECS")?;
+
+        Ok(())
+    }
+
+    fn validate(&self) -> StdResult<(), ConfigError> {
+        let invalid = |field, reason: &str| ConfigError::Invalid {
+            field,
+            reason: reason.to_string(),
+        };
+
+        if self.discord.token.is_empty() {
+            return Err(invalid("discord.token", "must be set"));
+        }
+        if let Sharding::Range { first, last, total } = self.discord.sharding
+            && !(first <= last && last < total)
+        {
+            return Err(invalid(
+                "discord.sharding",
+                "shards must satisfy first <= last < total",
+            ));
+        }
+        if self.mongodb.uri.is_empty() {
+            return Err(invalid("mongodb.uri", "must be set"));
+        }
+        if self.mongodb.dbname.is_empty() {
+            return Err(invalid("mongodb.dbname", "must be set"));
+        }
+        if let Err(err) = url::Url::parse(&self.steam.store_url) {
+            return Err(invalid("steam.store_url", &err.to_string()));
+        }
+        if let Err(err) = url::Url::parse(&self.steam.community_url) {
+            return Err(invalid("steam.community_url", &err.to_string()));
+        }
+        if self.steam.fetch_buffer_size == 0 {
+            return Err(invalid("steam.fetch_buffer_size", "must be at least 1"));
+        }
+        if self.steam.max_tries == 0 {
+            return Err(invalid("steam.max_tries", "must be at least 1"));
+        }
+        // Retrying without backing off would keep Steam rate limiting us.
+        if self.steam.retry_timeout_secs == 0 {
+            return Err(invalid("steam.retry_timeout_secs", "must be at least 1"));
+        }
+        if self.check.hour > 23 {
+            return Err(invalid("check.hour", "must be between 0 and 23"));
+        }
+        if self.check.minute > 59 {
+            return Err(invalid("check.minute", "must be between 0 and 59"));
+        }
+        // Pages are listed in a select menu, which holds at most 25 options.
+        if !(1..=25).contains(&self.commands.page_size) {
+            return Err(invalid("commands.page_size", "must be between 1 and 25"));
+        }
+        if !self.http.addr.is_empty()
+            && let Err(err) = self.http.addr.parse::<SocketAddr>()
+        {
+            return Err(invalid("http.addr", &err.to_string()));
+        }
+        if self.lease.ttl_secs < 3 {
+            return Err(invalid("lease.ttl_secs", "must be at least 3"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Sets the global config returned by [`get`]. Only the first call has an effect.
+pub fn init(config: Config) -> &'static Config {
+    CONFIG.get_or_init(|| config)
+}
+
+/// Gets the global config.
+///
+/// # Panics
+/// Panics if [`init`] wasn't called, except in tests where the defaults are used.
+pub fn get() -> &'static Config {
+    #[cfg(test)]
+    {
+        CONFIG.get_or_init(Config::default)
+    }
+    #[cfg(not(test))]
+    {
+        CONFIG
+            .get()
+            .expect("config should be initialized at startup")
+    }
+}
+
+/// Like [`util::env_var`], but `None` if the variable is unset.
+fn env_var<T: FromStr>(key: &str) -> StdResult<Option<T>, util::EnvVarError>
+where
+    T::Err: std::fmt::Display,
+{
+    match util::env_var(key) {
+        Ok(x) => Ok(Some(x)),
+        Err(util::EnvVarError::InvalidOrMissingKey { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn env_override<T: FromStr>(field: &mut T, key: &str) -> StdResult<(), util::EnvVarError>
+where
+    T::Err: std::fmt::Display,
+{
+    if let Some(x) = env_var(key)? {
+        *field = x;
+    }
+    Ok(())
+}
+
+/// Parses a comma-separated list, ignoring empty items.
+fn parse_list<T: FromStr>(s: &str) -> StdResult<Vec<T>, T::Err> {
+    s.split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Parses a hex color such as "#6B4C88".
+fn parse_color(s: &str) -> StdResult<serenity::Color, String> {
+    let hex = s.trim().trim_start_matches('#');
+    match u32::from_str_radix(hex, 16) {
+        Ok(x) if hex.len() == 6 => Ok(serenity::Color::new(x)),
+        _ => Err(format!("{s} is not a hex color like #6B4C88")),
+    }
+}
+
+fn deserialize_color<'de, D>(deserializer: D) -> StdResult<serenity::Color, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    parse_color(&s).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::{
+        Result,
+        config::{Config, ConfigError, Sharding, parse_color, parse_list},
+    };
+
+    /// The minimum config that passes validation.
+    const VALID_TOML: &str = r#"
+        [discord]
+        token = "token"
+
+        [mongodb]
+        uri = "mongodb://localhost:27017"
+        dbname = "steamsale"
+    "#;
+
+    #[rstest]
+    #[case("#6B4C88", Some(0x6B4C88))]
+    #[case("6b4c88", Some(0x6B4C88))]
+    #[case(" #000000 ", Some(0))]
+    #[case("#FFF", None)]
+    #[case("#6B4C8800", None)]
+    #[case("#GGGGGG", None)]
+    #[case("", None)]
+    fn parse_color_parses_hex_colors(#[case] input: &str, #[case] expected: Option<u32>) {
+        assert_eq!(expected, parse_color(input).ok().map(|c| c.0));
+    }
+
+    #[rstest]
+    #[case("1,2,3", Some(vec![1, 2, 3]))]
+    #[case(" 1 , 2 ,, ", Some(vec![1, 2]))]
+    #[case("", Some(vec![]))]
+    #[case("1,a", None)]
+    fn parse_list_ignores_empty_items(#[case] input: &str, #[case] expected: Option<Vec<u64>>) {
+        assert_eq!(expected, parse_list::<u64>(input).ok());
+    }
+
+    #[rstest]
+    #[case("single", Some(Sharding::Single))]
+    #[case("auto", Some(Sharding::Auto))]
+    #[case(" 0-3/8 ", Some(Sharding::Range { first: 0, last: 3, total: 8 }))]
+    #[case("4 - 7 / 8", Some(Sharding::Range { first: 4, last: 7, total: 8 }))]
+    #[case("0-3", None)]
+    #[case("3/8", None)]
+    #[case("a-b/c", None)]
+    #[case("", None)]
+    fn sharding_parses_modes_and_ranges(#[case] input: &str, #[case] expected: Option<Sharding>) {
+        assert_eq!(expected, input.parse().ok());
+    }
+
+    #[test]
+    fn toml_fills_unset_values_with_defaults() -> Result<()> {
+        let config: Config = toml::from_str(
+            r##"
+            [discord]
+            brand_color = "#112233"
+            sharding = { mode = "range", first = 0, last = 1, total = 4 }
+
+            [check]
+            hour = 3
+            "##,
+        )?;
+
+        assert_eq!(0x112233, config.discord.brand_color.0);
+        assert_eq!(
+            Sharding::Range {
+                first: 0,
+                last: 1,
+                total: 4
+            },
+            config.discord.sharding
+        );
+        assert_eq!(3, config.check.hour);
+        assert_eq!(0, config.check.minute);
+        assert_eq!(5, config.steam.max_tries);
+
+        Ok(())
+    }
+
+    #[test]
+    fn toml_rejects_unknown_fields() {
+        let res = toml::from_str::<Config>("[check]\nhuor = 3");
+        assert!(res.is_err(), "{res:?}");
+    }
+
+    #[test]
+    fn validate_accepts_valid_config() -> Result<()> {
+        let config: Config = toml::from_str(VALID_TOML)?;
+        config.validate()?;
+        Ok(())
+    }
+
+    #[test]
+    fn validate_accepts_disabled_http_server() -> Result<()> {
+        let mut config: Config = toml::from_str(VALID_TOML)?;
+        config.http.addr.clear();
+        config.validate()?;
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("discord.token", |c: &mut Config| c.discord.token.clear())]
+    #[case("discord.sharding", |c: &mut Config| c.discord.sharding = Sharding::Range { first: 2, last: 1, total: 4 })]
+    #[case("discord.sharding", |c: &mut Config| c.discord.sharding = Sharding::Range { first: 0, last: 4, total: 4 })]
+    #[case("mongodb.uri", |c: &mut Config| c.mongodb.uri.clear())]
+    #[case("steam.store_url", |c: &mut Config| c.steam.store_url = "not a url".to_string())]
+    #[case("steam.fetch_buffer_size", |c: &mut Config| c.steam.fetch_buffer_size = 0)]
+    #[case("steam.max_tries", |c: &mut Config| c.steam.max_tries = 0)]
+    #[case("steam.retry_timeout_secs", |c: &mut Config| c.steam.retry_timeout_secs = 0)]
+    #[case("check.hour", |c: &mut Config| c.check.hour = 24)]
+    #[case("check.minute", |c: &mut Config| c.check.minute = 60)]
+    #[case("commands.page_size", |c: &mut Config| c.commands.page_size = 0)]
+    #[case("commands.page_size", |c: &mut Config| c.commands.page_size = 26)]
+    #[case("http.addr", |c: &mut Config| c.http.addr = "localhost".to_string())]
+    #[case("lease.ttl_secs", |c: &mut Config| c.lease.ttl_secs = 2)]
+    fn validate_rejects_invalid_value(
+        #[case] expected: &str,
+        #[case] invalidate: fn(&mut Config),
+    ) -> Result<()> {
+        let mut config: Config = toml::from_str(VALID_TOML)?;
+        invalidate(&mut config);
+
+        match config.validate() {
+            Err(ConfigError::Invalid { field, .. }) => assert_eq!(expected, field),
+            res => panic!("Expected {expected} to be invalid: {res:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial_test::serial(env)]
+    fn load_overrides_file_with_env() -> Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("steamsale_config_{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            format!("{VALID_TOML}\n[check]\nhour = 3\nminute = 30"),
+        )?;
+
+        // SAFETY: Tests that touch the environment run serially.
+        unsafe {
+            std::env::set_var("CONFIG_FILE", &path);
+            std::env::set_var("DISCORD_TOKEN", "env token");
+            std::env::set_var("CHECK_MINUTE", "45");
+            std::env::remove_var("CHECK_HOUR");
+        }
+        let res = Config::load();
+        unsafe {
+            std::env::remove_var("CONFIG_FILE");
+            std::env::remove_var("DISCORD_TOKEN");
+            std::env::remove_var("CHECK_MINUTE");
+        }
+        std::fs::remove_file(&path)?;
+
+        let config = res?;
+        assert_eq!("env token", config.discord.token);
+        assert_eq!(3, config.check.hour);
+        assert_eq!(45, config.check.minute);
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial_test::serial(env)]
+    fn load_reports_invalid_env_value() {
+        // SAFETY: Tests that touch the environment run serially.
+        unsafe {
+            std::env::set_var("CONFIG_FILE", "/nonexistent/config.toml");
+        }
+        let missing_file = Config::load();
+        unsafe {
+            std::env::remove_var("CONFIG_FILE");
+            std::env::set_var("CHECK_HOUR", "noon");
+        }
+        let invalid_env = Config::load();
+        unsafe {
+            std::env::remove_var("CHECK_HOUR");
+        }
+
+        assert!(
+            matches!(missing_file, Err(ConfigError::Read { .. })),
+            "{missing_file:?}"
+        );
+        assert!(
+            matches!(invalid_env, Err(ConfigError::Env(_))),
+            "{invalid_env:?}"
+        );
+    }
+}
@@ -6,11 +6,16 @@ use mongodb::{
 };
 use tracing::info;
 
-use crate::models;
+use crate::{models, telemetry};
 
 pub const APPS_COLL: &str = "apps";
 pub const DISCORD_COLL: &str = "discord";
 pub const JUNCTION_COLL: &str = "junction";
+pub const HISTORY_COLL: &str = "history";
+pub const QUEUE_COLL: &str = "queue";
+pub const LEASES_COLL: &str = "leases";
+pub const JOBS_COLL: &str = "jobs";
+pub const CURSORS_COLL: &str = "cursors";
 
 #[derive(Clone)]
 pub struct Database {
@@ -38,15 +43,28 @@ impl Database {
             }
         };
         options.server_api = Some(ServerApi::builder().version(ServerApiVersion::V1).build());
+        options.command_event_handler = Some(telemetry::command_spans());
         let client = mongodb::Client::with_options(options)?;
 
-        let name = name.into();
-        let db = client.database(&name);
+        let db = Self {
+            name: name.into(),
+            client,
+        };
         info!("Pinging database...");
-        db.run_command(bson::doc! {"ping": 1}).await?;
+        db.ping().await?;
         info!("Pong received from database");
 
-        Ok(Self { name, client })
+        Ok(db)
+    }
+
+    /// Closes the client's connections once its sessions and cursors are dropped.
+    pub async fn shutdown(&self) {
+        self.client.clone().shutdown().await;
+    }
+
+    pub async fn ping(&self) -> mongodb::error::Result<()> {
+        self.db().run_command(bson::doc! {"ping": 1}).await?;
+        Ok(())
     }
 
     pub async fn start_session(&self) -> mongodb::error::Result<mongodb::ClientSession> {
@@ -65,6 +83,26 @@ impl Database {
         self.db().collection(APPS_COLL)
     }
 
+    pub fn history(&self) -> mongodb::Collection<models::Observation> {
+        self.db().collection(HISTORY_COLL)
+    }
+
+    pub fn queue(&self) -> mongodb::Collection<models::QueuedAlert> {
+        self.db().collection(QUEUE_COLL)
+    }
+
+    pub fn leases(&self) -> mongodb::Collection<models::Lease> {
+        self.db().collection(LEASES_COLL)
+    }
+
+    pub fn jobs(&self) -> mongodb::Collection<models::JobRun> {
+        self.db().collection(JOBS_COLL)
+    }
+
+    pub fn cursors(&self) -> mongodb::Collection<models::CheckCursor> {
+        self.db().collection(CURSORS_COLL)
+    }
+
     fn db(&self) -> mongodb::Database {
         self.client.database(&self.name)
     }
@@ -0,0 +1,296 @@
+//! This module provides the weekly digest, a summary of a guild's tracked apps
+//! built from stored app metadata and observation history.
+
+use std::collections::HashMap;
+
+use chrono::Datelike;
+use futures::{StreamExt, TryStreamExt};
+use poise::serenity_prelude as serenity;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::{Result, config, embeds, framework, models, repos, scheduler, util};
+
+/// Sends weekly digests to guilds whose scheduled weekday and hour is the
+/// current one in their timezone.
+#[tracing::instrument(level = "error", skip(ctx, cancel))]
+pub async fn send_due_weekly_digests(
+    ctx: &framework::Data,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let now = chrono::Utc::now();
+
+    let mut guilds = ctx.repo.discord.get_weekly_digest_guilds().await?;
+    while let Some(discord) = guilds.next().await {
+        if cancel.is_cancelled() {
+            Err(scheduler::Cancelled)?
+        }
+        let discord = match discord {
+            Ok(x) => x,
+            Err(err) => {
+                error!(?err, "Failed to get guild");
+                continue;
+            }
+        };
+        if !is_due(&discord, now) {
+            continue;
+        }
+        if ctx.dry_run {
+            info!(guild_id = discord.server_id, "Would send weekly digest");
+            continue;
+        }
+
+        info!(guild_id = discord.server_id, "Sending weekly digest");
+        if let Err(err) = send_weekly_digest(ctx, &discord, now).await {
+            error!(
+                ?err,
+                guild_id = discord.server_id,
+                "Failed to send weekly digest"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn is_due(discord: &models::Discord, now: chrono::DateTime<chrono::Utc>) -> bool {
+    let Some(scheduled) = last_scheduled_at(discord, now) else {
+        return false;
+    };
+    match discord.last_digest_at {
+        // Catches up on a digest missed while the bot was down.
+        Some(last) => util::to_chrono(last) < scheduled,
+        // Guilds that just switched to weekly wait for their first scheduled hour.
+        None => now - scheduled < chrono::Duration::hours(1),
+    }
+}
+
+/// When the guild's weekly digest was last scheduled at or before `now`. `None`
+/// if the guild isn't weekly or the scheduled hour was skipped by a DST change.
+fn last_scheduled_at(
+    discord: &models::Discord,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<chrono::DateTime<chrono::Utc>> {
+    let models::DeliveryMode::Weekly { weekday, hour } = discord.delivery_mode else {
+        return None;
+    };
+    let local = now.with_timezone(&discord.timezone).naive_local();
+    let days_back =
+        (7 + local.weekday().num_days_from_monday() - weekday.num_days_from_monday()) % 7;
+    let mut scheduled =
+        (local.date() - chrono::Days::new(days_back.into())).and_hms_opt(hour, 0, 0)?;
+    if scheduled > local {
+        scheduled -= chrono::TimeDelta::weeks(1);
+    }
+    scheduled
+        .and_local_timezone(discord.timezone)
+        .earliest()
+        .map(|x| x.to_utc())
+}
+
+async fn send_weekly_digest(
+    ctx: &framework::Data,
+    discord: &models::Discord,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    let channel = serenity::ChannelId::new(discord.channel_id.try_into()?);
+    let since = discord
+        .last_digest_at
+        .map(util::to_chrono)
+        .unwrap_or(now - chrono::Duration::weeks(1));
+
+    let mut embeds = create_weekly_digest(&ctx.repo, discord.server_id, since)
+        .await?
+        .into_iter();
+    let Some(first) = embeds.next() else {
+        return Ok(());
+    };
+    channel
+        .send_message(&ctx.http, serenity::CreateMessage::new().embed(first))
+        .await?;
+    // Recorded once the digest starts going out so a later failed send doesn't
+    // make the whole digest go out again next hour.
+    ctx.repo
+        .discord
+        .set_last_digest_at(discord.server_id, util::from_chrono(now))
+        .await?;
+
+    for embed in embeds {
+        channel
+            .send_message(&ctx.http, serenity::CreateMessage::new().embed(embed))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Creates embeds listing the guild's tracked apps that are on sale, along with
+/// releases and new lowest prices observed since `since`.
+async fn create_weekly_digest(
+    repo: &repos::Repo,
+    guild_id: i64,
+    since: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<serenity::CreateEmbed>> {
+    let since = util::from_chrono(since);
+
+    let app_ids = repo
+        .junction
+        .get_guild_junctions(guild_id)
+        .await?
+        .map_ok(|j| j.app_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+    let apps = repo
+        .apps
+        .get_apps(&app_ids)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let summaries = repo
+        .history
+        .get_summaries_before(&app_ids, since)
+        .await?
+        .into_iter()
+        .map(|s| (s.app_id, s))
+        .collect::<HashMap<_, _>>();
+    let mut recent = HashMap::<i32, Vec<models::Observation>>::new();
+    let mut observations = repo.history.get_observations_since(&app_ids, since).await?;
+    while let Some(observation) = observations.try_next().await? {
+        recent
+            .entry(observation.app_id)
+            .or_default()
+            .push(observation);
+    }
+
+    let mut on_sale = Vec::new();
+    let mut new_lows = Vec::new();
+    let mut releases = Vec::new();
+    for app in apps {
+        let summary = summaries.get(&app.app_id);
+        let observations = recent
+            .get(&app.app_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        if is_released(summary, observations) {
+            releases.push(app.clone());
+        }
+        if let Some(low) = new_low(summary, observations) {
+            new_lows.push(models::App {
+                price: Some(low.clone()),
+                ..app.clone()
+            });
+        }
+        if app.price.as_ref().is_some_and(|p| p.discount_percent > 0) {
+            on_sale.push(app);
+        }
+    }
+
+    let mut embeds = embeds::sale_digest_embeds("Weekly Digest: On Sale", &on_sale);
+    embeds.extend(embeds::sale_digest_embeds(
+        "Weekly Digest: New Lowest Prices",
+        &new_lows,
+    ));
+    embeds.extend(embeds::release_digest_embeds(
+        "Weekly Digest: New Releases",
+        &releases,
+    ));
+    if embeds.is_empty() {
+        embeds.push(
+            serenity::CreateEmbed::new()
+                .title("Weekly Digest")
+                .description("None of the tracked apps are on sale this week.")
+                .color(config::get().discord.brand_color),
+        );
+    }
+
+    Ok(embeds)
+}
+
+/// Whether the app went from coming soon to released within `observations`.
+fn is_released(
+    summary: Option<&models::ObservationSummary>,
+    observations: &[models::Observation],
+) -> bool {
+    let mut coming_soon = summary.is_some_and(|s| s.coming_soon);
+    for observation in observations {
+        if coming_soon && !observation.coming_soon {
+            return true;
+        }
+        coming_soon = observation.coming_soon;
+    }
+    false
+}
+
+/// Gets the lowest discounted price within `observations` if it's lower than any
+/// price observed before them.
+fn new_low<'a>(
+    summary: Option<&models::ObservationSummary>,
+    observations: &'a [models::Observation],
+) -> Option<&'a models::Price> {
+    let previous_low = summary?.lowest_price?;
+    observations
+        .iter()
+        .filter_map(|o| o.price.as_ref())
+        .filter(|p| p.discount_percent > 0)
+        .min_by_key(|p| p.final_price)
+        .filter(|p| p.final_price < previous_low)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::{digest::is_due, models, util};
+
+    /// Weekly on Mondays at 09:00 UTC, such as 2026-10-19T09:00:00Z.
+    #[rstest]
+    #[case::on_schedule("2026-10-19T09:05:00Z", None, true)]
+    #[case::before_schedule("2026-10-19T08:55:00Z", None, false)]
+    #[case::first_digest_waits_for_schedule("2026-10-19T10:00:00Z", None, false)]
+    #[case::already_sent("2026-10-19T09:30:00Z", Some("2026-10-19T09:05:00Z"), false)]
+    #[case::sent_last_week("2026-10-19T09:05:00Z", Some("2026-10-12T09:05:00Z"), true)]
+    #[case::catches_up_missed_digest("2026-10-21T15:00:00Z", Some("2026-10-12T09:05:00Z"), true)]
+    #[case::caught_up("2026-10-22T15:00:00Z", Some("2026-10-21T15:00:00Z"), false)]
+    fn is_due_sends_once_per_week(
+        #[case] now: &str,
+        #[case] last_digest_at: Option<&str>,
+        #[case] expected: bool,
+    ) {
+        let at = |s: &str| s.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        let discord = models::Discord {
+            delivery_mode: models::DeliveryMode::Weekly {
+                weekday: chrono::Weekday::Mon,
+                hour: 9,
+            },
+            last_digest_at: last_digest_at.map(|s| util::from_chrono(at(s))),
+            ..Default::default()
+        };
+
+        assert_eq!(expected, is_due(&discord, at(now)));
+    }
+
+    #[test]
+    fn is_due_uses_guild_timezone() {
+        let discord = models::Discord {
+            delivery_mode: models::DeliveryMode::Weekly {
+                weekday: chrono::Weekday::Mon,
+                hour: 9,
+            },
+            timezone: chrono_tz::Asia::Tokyo,
+            ..Default::default()
+        };
+        let now = "2026-10-19T00:05:00Z".parse().unwrap();
+
+        assert!(is_due(&discord, now));
+    }
+
+    #[test]
+    fn is_due_skips_other_delivery_modes() {
+        let discord = models::Discord::default();
+        let now = "2026-10-19T09:05:00Z".parse().unwrap();
+
+        assert!(!is_due(&discord, now));
+    }
+}
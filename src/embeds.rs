@@ -0,0 +1,356 @@
+//! This module provides embeds shared by commands and events.
+
+use poise::serenity_prelude as serenity;
+
+use crate::{calendar, config, models, steam};
+
+pub fn released_embed(app: &steam::App) -> serenity::CreateEmbed {
+    let title = format!("{} has released on Steam!", app.name);
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    let price = app
+        .price_overview
+        .as_ref()
+        .map(|p| p.final_formatted.clone())
+        .unwrap_or("Free".to_string());
+
+    let mut fields = vec![("Price", price, false)];
+    if !app.description.is_empty() {
+        fields.push(("Description", app.description.clone(), false));
+    }
+
+    serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(app.header_image.clone())
+        .fields(fields)
+        .color(config::get().discord.brand_color)
+}
+
+pub fn sale_embed(
+    app: &steam::App,
+    ratings: &steam::Ratings,
+    event: Option<&str>,
+) -> serenity::CreateEmbed {
+    let price = app
+        .price_overview
+        .as_ref()
+        .expect("should have checked before called this fn");
+
+    let title = format!("{} is {}% off!", app.name, price.discount_percent);
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    let mut fields = vec![
+        ("Original Price", price.initial_formatted.clone(), true),
+        ("Sale Price", price.final_formatted.clone(), true),
+    ];
+    if let Some(fullgame) = &app.fullgame {
+        fields.push(("DLC For", fullgame.name.clone(), true));
+    }
+    match (&ratings.reviews, &app.recommendations) {
+        (Some(reviews), _) => {
+            let value = match reviews.positive_percent() {
+                Some(percent) => format!("{} ({percent}% positive)", reviews.review_score_desc),
+                None => reviews.review_score_desc.clone(),
+            };
+            fields.push(("Reviews", value, true));
+        }
+        (None, Some(recs)) => fields.push(("Reviews", recs.total.to_string(), true)),
+        (None, None) => {}
+    }
+    if let Some(deck) = ratings.deck {
+        fields.push(("Steam Deck", deck.to_string(), true));
+    }
+    if !app.description.is_empty() {
+        fields.push(("Description", app.description.clone(), false));
+    }
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(&app.header_image)
+        .fields(fields)
+        .color(sale_color(price.discount_percent));
+    if let Some(event) = event {
+        embed = embed.footer(serenity::CreateEmbedFooter::new(format!(
+            "Part of the {event}"
+        )));
+    }
+    embed
+}
+
+pub fn sale_ending_embed(app: &models::App) -> serenity::CreateEmbed {
+    let price = app
+        .price
+        .as_ref()
+        .expect("should have checked before called this fn");
+
+    let title = format!(
+        "Last chance: {} is {}% off!",
+        app.app_name, price.discount_percent
+    );
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    let mut embed = serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(&app.header_image)
+        .fields([
+            ("Original Price", price.initial_formatted.clone(), true),
+            ("Sale Price", price.final_formatted.clone(), true),
+        ])
+        .color(sale_color(price.discount_percent));
+    if let Some(end) = price.discount_end {
+        embed = embed.description(format!("Sale ends <t:{}:R>", end.timestamp_millis() / 1000));
+    }
+    embed
+}
+
+pub fn price_change_embed(app: &steam::App, old_price: &models::Price) -> serenity::CreateEmbed {
+    let price = app
+        .price_overview
+        .as_ref()
+        .expect("should have checked before called this fn");
+
+    let direction = if price.initial_price > old_price.initial_price {
+        "increased"
+    } else {
+        "decreased"
+    };
+    let title = format!("The price of {} has {direction}", app.name);
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(&app.header_image)
+        .fields([
+            ("Old Price", old_price.initial_formatted.clone(), true),
+            ("New Price", price.initial_formatted.clone(), true),
+        ])
+        .color(config::get().discord.brand_color)
+}
+
+pub fn delisted_embed(app: &models::App) -> serenity::CreateEmbed {
+    let title = format!("{} is no longer available on Steam", app.app_name);
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(&app.header_image)
+        .description(
+            "The app was delisted or removed from the store, or is unavailable in this region.",
+        )
+        .color(config::get().discord.brand_color)
+}
+
+pub fn relisted_embed(app: &steam::App) -> serenity::CreateEmbed {
+    let title = format!("{} is available on Steam again!", app.name);
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(&app.header_image)
+        .color(config::get().discord.brand_color)
+}
+
+pub fn early_access_embed(app: &steam::App, entered: bool) -> serenity::CreateEmbed {
+    let title = if entered {
+        format!("{} has entered Early Access!", app.name)
+    } else {
+        format!("{} has left Early Access!", app.name)
+    };
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    let price = app
+        .price_overview
+        .as_ref()
+        .map(|p| p.final_formatted.clone())
+        .unwrap_or("Free".to_string());
+
+    serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(&app.header_image)
+        .field("Price", price, false)
+        .color(config::get().discord.brand_color)
+}
+
+pub fn release_date_change_embed(app: &steam::App, old_date: &str) -> serenity::CreateEmbed {
+    let became_concrete = steam::parse_release_date(old_date).is_none()
+        && steam::parse_release_date(&app.release_date.date).is_some();
+    let title = if became_concrete {
+        format!("{} now has a release date!", app.name)
+    } else {
+        format!("The release date of {} has changed", app.name)
+    };
+    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
+
+    serenity::CreateEmbed::new()
+        .title(title)
+        .url(url)
+        .image(&app.header_image)
+        .fields([
+            ("Old Release Date", old_date.to_string(), true),
+            ("New Release Date", app.release_date.date.clone(), true),
+        ])
+        .color(config::get().discord.brand_color)
+}
+
+pub fn sale_event_embed(event: &calendar::SaleEvent) -> serenity::CreateEmbed {
+    let mut description = format!("Runs until <t:{}:f>.", event.end.timestamp());
+    if event.kind == calendar::EventKind::Sale {
+        description.push_str(" Until then, alerts are grouped into a digest.");
+    }
+    serenity::CreateEmbed::new()
+        .title(format!("The {} has started!", event.name))
+        .url("https://store.steampowered.com")
+        .description(description)
+        .color(config::get().discord.brand_color)
+}
+
+/// Gets an embed color for a discount, ranging from green for small discounts
+/// to red for large ones.
+pub fn sale_color(discount_percent: i32) -> u32 {
+    if discount_percent <= 5 {
+        0x0bff33
+    } else if discount_percent <= 10 {
+        0x44fdd2
+    } else if discount_percent <= 15 {
+        0x44fdfd
+    } else if discount_percent <= 20 {
+        0x44dbfd
+    } else if discount_percent <= 25 {
+        0x44b6fd
+    } else if discount_percent <= 30 {
+        0x448bfd
+    } else if discount_percent <= 35 {
+        0x445afd
+    } else if discount_percent <= 40 {
+        0x8544fd
+    } else if discount_percent <= 45 {
+        0xb044fd
+    } else if discount_percent <= 50 {
+        0xe144fd
+    } else if discount_percent <= 55 {
+        0xfd44de
+    } else if discount_percent <= 60 {
+        0xff23a7
+    } else if discount_percent <= 99 {
+        0xff0000
+    } else {
+        0xFFFFFF
+    }
+}
+
+/// Maximum number of apps listed in a single digest embed.
+const DIGEST_PAGE_SIZE: usize = 10;
+
+/// Creates embeds summarizing multiple sales, ordered by highest discount first.
+/// Each embed lists at most [`DIGEST_PAGE_SIZE`] apps.
+pub fn sale_digest_embeds(title: &str, apps: &[models::App]) -> Vec<serenity::CreateEmbed> {
+    let mut sales = apps
+        .iter()
+        .filter_map(|app| app.price.as_ref().map(|price| (app, price)))
+        .collect::<Vec<_>>();
+    sales.sort_by_key(|(_, price)| std::cmp::Reverse(price.discount_percent));
+
+    let pages = sales.chunks(DIGEST_PAGE_SIZE).collect::<Vec<_>>();
+    pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let fields = page.iter().map(|(app, price)| {
+                let name = match &app.fullgame_name {
+                    Some(fullgame) => format!(
+                        "{} (DLC for {fullgame}) is {}% off!",
+                        app.app_name, price.discount_percent
+                    ),
+                    None => format!("{} is {}% off!", app.app_name, price.discount_percent),
+                };
+                let value = format!(
+                    "Original Price: ~~{}~~\nSale Price: **{}**\n[Store Page](https://store.steampowered.com/app/{})",
+                    price.initial_formatted, price.final_formatted, app.app_id
+                );
+                (name, value, false)
+            });
+            let top_discount = page
+                .first()
+                .map(|(_, price)| price.discount_percent)
+                .unwrap_or_default();
+
+            serenity::CreateEmbed::new()
+                .title(format!("{title} {}/{}", i + 1, pages.len()))
+                .fields(fields)
+                .color(sale_color(top_discount))
+        })
+        .collect()
+}
+
+/// Creates embeds summarizing base price changes of multiple apps from their
+/// paired old price. Each embed lists at most [`DIGEST_PAGE_SIZE`] apps.
+pub fn price_change_digest_embeds(
+    title: &str,
+    changes: &[(models::App, models::Price)],
+) -> Vec<serenity::CreateEmbed> {
+    let pages = changes.chunks(DIGEST_PAGE_SIZE).collect::<Vec<_>>();
+    pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let description = page
+                .iter()
+                .filter_map(|(app, old_price)| {
+                    let price = app.price.as_ref()?;
+                    Some(format!(
+                        "[{}](https://store.steampowered.com/app/{}) - ~~{}~~ {}",
+                        app.app_name,
+                        app.app_id,
+                        old_price.initial_formatted,
+                        price.initial_formatted
+                    ))
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            serenity::CreateEmbed::new()
+                .title(format!("{title} {}/{}", i + 1, pages.len()))
+                .description(description)
+                .color(config::get().discord.brand_color)
+        })
+        .collect()
+}
+
+/// Creates embeds summarizing multiple releases. Each embed lists at most
+/// [`DIGEST_PAGE_SIZE`] apps.
+pub fn release_digest_embeds(title: &str, apps: &[models::App]) -> Vec<serenity::CreateEmbed> {
+    let pages = apps.chunks(DIGEST_PAGE_SIZE).collect::<Vec<_>>();
+    pages
+        .iter()
+        .enumerate()
+        .map(|(i, page)| {
+            let description = page
+                .iter()
+                .map(|app| {
+                    let price = app
+                        .price
+                        .as_ref()
+                        .map(|p| p.final_formatted.clone())
+                        .unwrap_or("Free".to_string());
+                    format!(
+                        "[{}](https://store.steampowered.com/app/{}) - {price}",
+                        app.app_name, app.app_id
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            serenity::CreateEmbed::new()
+                .title(format!("{title} {}/{}", i + 1, pages.len()))
+                .description(description)
+                .color(config::get().discord.brand_color)
+        })
+        .collect()
+}
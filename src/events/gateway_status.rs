@@ -0,0 +1,30 @@
+use poise::serenity_prelude as serenity;
+use tracing::warn;
+
+use crate::health;
+
+pub struct GatewayStatus;
+
+#[serenity::async_trait]
+impl serenity::EventHandler for GatewayStatus {
+    async fn ready(&self, ctx: serenity::Context, _ready: serenity::Ready) {
+        health::HEALTH.set_shard_connected(ctx.shard_id.0, true);
+    }
+
+    async fn resume(&self, ctx: serenity::Context, _resumed: serenity::ResumedEvent) {
+        health::HEALTH.set_shard_connected(ctx.shard_id.0, true);
+    }
+
+    /// Tracks whether the gateway is connected for health checks.
+    async fn shard_stage_update(
+        &self,
+        _ctx: serenity::Context,
+        event: serenity::ShardStageUpdateEvent,
+    ) {
+        let connected = event.new == serenity::ConnectionStage::Connected;
+        if !connected {
+            warn!(shard = ?event.shard_id, stage = ?event.new, "Gateway not connected");
+        }
+        health::HEALTH.set_shard_connected(event.shard_id.0, connected);
+    }
+}
@@ -1,7 +1,7 @@
 use poise::serenity_prelude as serenity;
 use tracing::error;
 
-use crate::util::PoiseData;
+use crate::{metrics, util::PoiseData};
 
 pub struct GuildAvailable;
 
@@ -14,6 +14,8 @@ impl serenity::EventHandler for GuildAvailable {
         guild: serenity::Guild,
         _is_new: Option<bool>,
     ) {
+        metrics::METRICS.guilds.set(ctx.cache.guild_count() as i64);
+
         let guild_id: i64 = guild.id.into();
         let channel_id: i64 = default_text_channel(&ctx, &guild)
             .await
@@ -8,3 +8,6 @@ pub use removed_from_guild::*;
 
 mod guild_available;
 pub use guild_available::*;
+
+mod gateway_status;
+pub use gateway_status::*;
@@ -2,7 +2,7 @@ use anyhow::Context;
 use poise::serenity_prelude as serenity;
 use tracing::info;
 
-use crate::{Result,repos, util::PoiseData};
+use crate::{Result, metrics, repos, util::PoiseData};
 
 pub struct RemovedFromGuild;
 
@@ -15,6 +15,7 @@ impl serenity::EventHandler for RemovedFromGuild {
         incomplete: serenity::UnavailableGuild,
         _full: Option<serenity::Guild>,
     ) {
+        metrics::METRICS.guilds.set(ctx.cache.guild_count() as i64);
         if incomplete.unavailable {
             return;
         }
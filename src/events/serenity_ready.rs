@@ -1,31 +1,45 @@
-use std::{sync::Arc, time::Duration};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use anyhow::Context;
-use futures::StreamExt;
+use futures::{StreamExt, TryStreamExt};
+use mongodb::bson;
 use once_map::OnceMap;
 use poise::serenity_prelude as serenity;
 use tokio::sync::OnceCell;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
 use crate::{
-    Result, StdResult, config, database,
+    Result, StdResult,
+    alerts::{self, Alert, Outbox},
+    calendar, config, database,
     framework::{self, Data},
-    models, repos, steam,
+    health, lease, metrics, models, repos, scheduler, shutdown, steam,
     util::{self, PoiseData},
 };
 
 static INIT: OnceCell<()> = OnceCell::const_new();
+/// Steam occasionally fails to return an app that exists, so it must be missing
+/// from this many checks in a row to be alerted as delisted.
+const MISSING_CHECKS_UNTIL_DELISTED: i32 = 3;
+/// Names the cursor that lets an interrupted check be resumed.
+const CHECK_CURSOR: &str = "check_apps";
+/// Held while apps are being checked so checks don't overlap.
+static CHECK_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
 
 pub struct SerenityReady;
 
 #[serenity::async_trait]
 impl serenity::EventHandler for SerenityReady {
-    /// Initialize framework data and start check apps loop.
+    /// Initialize framework data and start the scheduled jobs once per process.
+    /// The jobs only run while the process holds the [`lease::LEASE`], so they
+    /// run once across all shards and processes.
     async fn ready(&self, ctx: serenity::Context, _ready: serenity::Ready) {
-        info!("Received SerenityReady");
+        info!(shard = ctx.shard_id.0, "Received SerenityReady");
         INIT.get_or_init(|| async {
             init_data(&ctx).await;
-            init_check_apps(ctx.poise_data_unwrap().await);
+            init_lease(ctx.poise_data_unwrap().await);
+            scheduler::SCHEDULER.start(ctx.poise_data_unwrap().await);
         })
         .await;
     }
@@ -43,171 +57,546 @@ async fn init_data(ctx: &serenity::Context) {
 }
 
 async fn create_data(http: Arc<serenity::Http>) -> Result<Data> {
+    let config = config::get();
     let repo = {
-        let uri: String = util::env_var("MONGODB_URI")?;
-        let name: String = util::env_var("MONGODB_DBNAME")?;
-        let db = database::Database::new(&uri, name).await?;
+        let db = database::Database::new(&config.mongodb.uri, &config.mongodb.dbname).await?;
 
         repos::Repo::new(Arc::new(db))
     };
+    repo.history.create_indexes().await?;
+    health::HEALTH.set_repo(repo.clone());
 
-    let steam = {
-        let store = "https://store.steampowered.com";
-        let community = "https://steamcommunity.com";
+    let steam = steam::Client::new(
+        &config.steam.store_url,
+        &config.steam.community_url,
+        config.steam.fetch_buffer_size,
+    );
 
-        steam::Client::new(store, community)
-    };
+    let calendar = calendar::SaleCalendar::load()?;
+
+    let dry_run = config.check.dry_run;
+    if dry_run {
+        warn!("Dry run mode is on. Checks won't update junctions or send alerts");
+    }
 
-    Ok(Data { http, repo, steam })
+    Ok(Data {
+        http,
+        repo,
+        steam,
+        calendar,
+        dry_run,
+    })
 }
 
-fn init_check_apps(ctx: Arc<framework::Data>) {
+fn init_lease(ctx: Arc<framework::Data>) {
     tokio::spawn(async move {
-        loop {
-            tokio::time::sleep_until(checking_time()).await;
-
-            info!("Checking apps...");
-            if let Err(err) = check_apps(&ctx).await {
-                error!(?err, "Failed to check apps");
-            }
-        }
+        lease::LEASE.keep_renewed(ctx.repo.clone()).await;
     });
 }
 
-fn checking_time() -> tokio::time::Instant {
-    let now = chrono::Utc::now().naive_utc();
+/// Checks apps unless a check is already running, in which case `None` is returned.
+/// Returns the alerts found for each guild. Stops before the next app once `cancel`
+/// is cancelled or the lease is lost, failing with [`scheduler::Cancelled`].
+///
+/// With `dry_run`, nothing is written to the database and no alerts are sent.
+/// The alerts that would have been sent are logged instead.
+pub async fn run_check(
+    ctx: &framework::Data,
+    dry_run: bool,
+    cancel: &CancellationToken,
+) -> Option<Result<HashMap<i64, Vec<Alert>>>> {
+    let _guard = CHECK_LOCK.try_lock().ok()?;
+
+    info!(dry_run, "Checking apps...");
+    let timer = metrics::METRICS.check_duration.start_timer();
+    let res = check_apps(ctx, dry_run, cancel).await;
+    // A dry run isn't a real check, so it's left out of the health and metrics.
+    if dry_run {
+        timer.stop_and_discard();
+    } else {
+        timer.observe_duration();
+    }
+    match &res {
+        Ok(alerts) if dry_run => info!("Dry run report:\n{}", alerts::report(alerts)),
+        Ok(_) => health::HEALTH.record_check(chrono::Utc::now()),
+        Err(err) if err.is::<scheduler::Cancelled>() => warn!("Check was interrupted"),
+        Err(err) => error!(?err, "Failed to check apps"),
+    }
+
+    Some(res)
+}
+
+pub fn is_checking() -> bool {
+    CHECK_LOCK.try_lock().is_err()
+}
 
-    let today_17 = now
-        .date()
-        .and_hms_opt(17, 0, 0)
-        .expect("should be valid hms");
+/// Waits for the running check, if any, to finish.
+pub async fn wait_for_check() {
+    drop(CHECK_LOCK.lock().await);
+}
 
-    let next_17 = if now < today_17 {
-        today_17
-    } else {
-        today_17 + chrono::Duration::days(1)
+#[tracing::instrument(level = "error", skip(ctx, cancel))]
+async fn check_apps(
+    ctx: &framework::Data,
+    dry_run: bool,
+    cancel: &CancellationToken,
+) -> Result<HashMap<i64, Vec<Alert>>> {
+    let mut app_ids = ctx.repo.apps.get_app_ids().await?;
+    app_ids.sort_unstable();
+    // Dry runs neither resume a check nor leave one to resume.
+    let resumed = match dry_run {
+        true => None,
+        false => interrupted_check(ctx).await?,
     };
+    if let Some(cursor) = &resumed {
+        info!(
+            last_app_id = cursor.last_app_id,
+            "Resuming interrupted check"
+        );
+        app_ids.retain(|&app_id| app_id > cursor.last_app_id);
+    }
+    let mut cursor = resumed.unwrap_or_else(|| models::CheckCursor {
+        name: CHECK_CURSOR.to_string(),
+        started_at: bson::DateTime::now(),
+        last_app_id: 0,
+    });
+
+    let discord_cache = OnceMap::new();
+    let outbox = Outbox::default();
+    let mut interrupted = false;
+    for app_id in app_ids {
+        // Apps are checked one at a time, so stopping here leaves no junction half-updated.
+        if cancel.is_cancelled() {
+            warn!(app_id, "Stopping check since it was cancelled");
+            interrupted = true;
+            break;
+        }
+        if !dry_run && !lease::LEASE.is_held() {
+            warn!(app_id, "Stopping check since the lease was lost");
+            interrupted = true;
+            break;
+        }
+
+        let observation = check_app(ctx, app_id, &discord_cache, &outbox, dry_run).await?;
+        if !dry_run {
+            cursor.last_app_id = app_id;
+            ctx.repo
+                .cursors
+                .set_cursor(&cursor)
+                .await
+                .inspect_err(|err| error!(?err, app_id, "Failed to save check cursor"))
+                .ok();
+        }
+        // Recorded last so a resumed check that checks the app again still
+        // compares against the previous check's observation.
+        if !dry_run && let Some(observation) = observation {
+            ctx.repo
+                .history
+                .add_observation(&observation)
+                .await
+                .inspect_err(|err| error!(?err, app_id, "Failed to record observation"))
+                .ok();
+        }
+    }
 
-    let duration = (next_17 - now)
-        .to_std()
-        .expect("should be positive duration");
+    if interrupted {
+        // The alerts were queued as apps were checked, so the lease holder delivers them.
+        Err(scheduler::Cancelled)?;
+    }
+    let alerts = outbox.into_inner();
+    if dry_run {
+        return Ok(alerts);
+    }
+    ctx.repo
+        .cursors
+        .remove_cursor(CHECK_CURSOR)
+        .await
+        .inspect_err(|err| error!(?err, "Failed to remove check cursor"))
+        .ok();
 
-    tokio::time::Instant::now() + duration
+    // Alerts that fail to deliver stay queued and are retried hourly.
+    let now = chrono::Utc::now();
+    for &guild_id in alerts.keys() {
+        if cancel.is_cancelled() {
+            warn!("Stopping delivery since it was cancelled. The rest stay queued");
+            Err(scheduler::Cancelled)?;
+        }
+        if let Err(err) = alerts::deliver_queued_to_guild(ctx, guild_id, now).await {
+            error!(?err, guild_id, "Failed to deliver alerts");
+        }
+    }
+
+    Ok(alerts)
 }
 
-#[tracing::instrument(level = "error", skip(ctx))]
-async fn check_apps(ctx: &framework::Data) -> Result<()> {
+/// Checks the app and alerts the guilds tracking it of any changes. Returns the
+/// observation to record, or `None` if the app couldn't be fetched.
+async fn check_app(
+    ctx: &framework::Data,
+    app_id: i32,
+    discord_cache: &OnceMap<i64, Arc<models::Discord>>,
+    outbox: &Outbox,
+    dry_run: bool,
+) -> Result<Option<models::Observation>> {
     let apps_repo = &ctx.repo.apps;
     let junc_repo = &ctx.repo.junction;
 
-    apps_repo
-        .remove_orphans()
-        .await
-        .inspect_err(|err| error!(?err, "Failed to remove orphans"))
-        .ok();
-
-    let discord_cache = OnceMap::new();
-    for app_id in apps_repo.get_app_ids().await? {
-        let app = match get_app(&ctx.steam, app_id).await {
-            Ok(Some(app)) => app,
-            Ok(None) => {
-                error!(app_id, "App not found");
-                continue;
-            }
-            Err(err) => {
-                error!(?err, app_id, "Failed to fetch app");
-                continue;
+    let app = match get_app(&ctx.steam, app_id).await {
+        Ok(Some(app)) => app,
+        Ok(None) => {
+            warn!(app_id, "App not found");
+            if let Err(err) = notify_delisted(ctx, app_id, discord_cache, outbox, dry_run).await {
+                error!(?err, app_id, "Failed to notify guilds of delisted app");
             }
-        };
-
-        junc_repo
-            .get_junctions(app_id)
-            .await?
-            .for_each_concurrent(None, |junction| async {
-                match junction {
-                    Ok(j) => {
-                        let guild_id = j.server_id;
-                        let app_id = j.app_id;
-
-                        if let Err(err) = notify_guild(ctx, j, &discord_cache, &app).await {
-                            error!(?err, "Failed to notify guild");
-                            return;
-                        }
-                        if app.is_free && !app.release_date.coming_soon {
-                            if let Err(err) = junc_repo.remove_junction(guild_id, app_id).await {
-                                error!(?err, "Failed to remove free and released app");
-                            }
-                        }
-                    }
-                    Err(err) => error!(?err, "Failed to get junction"),
-                };
-            })
-            .await;
+            return Ok(None);
+        }
+        Err(err) => {
+            error!(?err, app_id, "Failed to fetch app");
+            return Ok(None);
+        }
+    };
+    if !dry_run {
+        metrics::METRICS.apps_checked.inc();
+        apps_repo
+            .upsert_app(&app.clone().into())
+            .await
+            .inspect_err(|err| error!(?err, app_id, "Failed to refresh app"))
+            .ok();
     }
+    let previous = ctx
+        .repo
+        .history
+        .get_latest_observation(app_id)
+        .await
+        .inspect_err(|err| error!(?err, app_id, "Failed to get previous observation"))
+        .ok()
+        .flatten();
+
+    // Only fetched if a guild is due a sale alert.
+    let ratings = OnceCell::new();
+    junc_repo
+        .get_junctions(app_id)
+        .await?
+        .for_each_concurrent(None, |junction| async {
+            match junction {
+                Ok(j) => {
+                    let guild_id = j.server_id;
+                    let app_id = j.app_id;
+
+                    let checked = CheckedApp {
+                        app: &app,
+                        previous: previous.as_ref(),
+                        ratings: &ratings,
+                    };
+                    let notify = notify_guild(ctx, j, discord_cache, outbox, checked, dry_run);
+                    if let Err(err) = notify.await {
+                        error!(?err, "Failed to notify guild");
+                        return;
+                    }
+                    if !dry_run
+                        && app.is_free
+                        && !app.release_date.coming_soon
+                        && let Err(err) = junc_repo.remove_junction(guild_id, app_id).await
+                    {
+                        error!(?err, "Failed to remove free and released app");
+                    }
+                }
+                Err(err) => error!(?err, "Failed to get junction"),
+            };
+        })
+        .await;
 
-    Ok(())
+    Ok(Some((&app).into()))
+}
+
+/// Gets the cursor of a check that was interrupted recently enough to resume.
+pub async fn interrupted_check(
+    ctx: &framework::Data,
+) -> mongodb::error::Result<Option<models::CheckCursor>> {
+    // Cursors older than the check's timeout belong to checks that are over.
+    let resumable_for = chrono::TimeDelta::from_std(scheduler::CHECK_APPS_TIMEOUT)
+        .expect("check timeout should fit in a TimeDelta");
+    let cursor = ctx.repo.cursors.get_cursor(CHECK_CURSOR).await?;
+    Ok(cursor
+        .filter(|cursor| chrono::Utc::now() - util::to_chrono(cursor.started_at) < resumable_for))
 }
 
 async fn get_app(
     steam: &steam::Client,
     app_id: i32,
 ) -> StdResult<Option<steam::App>, steam::FetchError> {
+    with_backoff(app_id, || steam.app_details(app_id)).await
+}
+
+/// Fetches the app's [`steam::Ratings`]. Parts that fail to fetch are left as `None`.
+async fn get_ratings(steam: &steam::Client, app_id: i32) -> steam::Ratings {
+    let (reviews, deck) = tokio::join!(
+        with_backoff(app_id, || steam.app_reviews(app_id)),
+        with_backoff(app_id, || steam.deck_compatibility(app_id)),
+    );
+    steam::Ratings {
+        reviews: reviews
+            .inspect_err(|err| error!(app_id, ?err, "Failed to fetch reviews"))
+            .ok()
+            .flatten(),
+        deck: deck
+            .inspect_err(|err| error!(app_id, ?err, "Failed to fetch deck compatibility"))
+            .ok()
+            .flatten(),
+    }
+}
+
+/// Fetches with `fetch`, backing off and retrying while rate-limited by Steam.
+async fn with_backoff<T, F>(
+    app_id: i32,
+    mut fetch: impl FnMut() -> F,
+) -> StdResult<T, steam::FetchError>
+where
+    F: Future<Output = StdResult<T, steam::FetchError>>,
+{
+    let config = &config::get().steam;
     let mut tries = 0;
-    const MAX_TRIES: u32 = 5;
-    const RETRY_TIMEOUT: u64 = 300;
 
-    let mut app_res = steam.app_details(app_id).await;
-    while matches!(&app_res, Err(err) if err.is_rate_limited()) {
+    let mut res = fetch().await;
+    while matches!(&res, Err(err) if err.is_rate_limited()) {
         info!("Steam rate-limit hit. Temporarily backing off...");
-        tokio::time::sleep(Duration::from_secs(RETRY_TIMEOUT)).await;
-        app_res = steam.app_details(app_id).await;
+        metrics::METRICS.rate_limit_backoffs.inc();
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(config.retry_timeout_secs)) => {}
+            _ = shutdown::SHUTDOWN.cancelled() => break,
+        }
+        res = fetch().await;
 
-        if tries >= MAX_TRIES {
+        if tries >= config.max_tries {
             warn!("Rate limited too many times. No longer retrying app {app_id}");
             break;
         }
         tries += 1;
     }
 
-    app_res
+    res
+}
+
+/// Checks a single app without updating its junctions or sending anything,
+/// returning the alerts each guild would be sent. `None` if the app doesn't exist.
+pub async fn dry_run_app(
+    ctx: &framework::Data,
+    app_id: i32,
+) -> Result<Option<HashMap<i64, Vec<Alert>>>> {
+    let Some(app) = ctx.steam.app_details(app_id).await? else {
+        return Ok(None);
+    };
+    let previous = ctx.repo.history.get_latest_observation(app_id).await?;
+
+    let discord_cache = OnceMap::new();
+    let outbox = Outbox::default();
+    let ratings = OnceCell::new();
+    let mut junctions = ctx.repo.junction.get_junctions(app_id).await?;
+    while let Some(junction) = junctions.try_next().await? {
+        let checked = CheckedApp {
+            app: &app,
+            previous: previous.as_ref(),
+            ratings: &ratings,
+        };
+        notify_guild(ctx, junction, &discord_cache, &outbox, checked, true).await?;
+    }
+
+    Ok(Some(outbox.into_inner()))
+}
+
+/// An app fetched during a check along with what's shared between the guilds
+/// tracking it.
+#[derive(Clone, Copy)]
+struct CheckedApp<'a> {
+    app: &'a steam::App,
+    /// The app's observation from the last check, if any.
+    previous: Option<&'a models::Observation>,
+    /// The app's ratings, fetched at most once.
+    ratings: &'a OnceCell<steam::Ratings>,
 }
 
+/// Collects the alerts warranted by the checked app for the junction's guild into
+/// `outbox`. Unless `dry_run`, queues them for delivery and updates the junction's
+/// state.
 async fn notify_guild(
     ctx: &framework::Data,
     mut junction: models::Junction,
     discord_cache: &OnceMap<i64, Arc<models::Discord>>,
-    app: &steam::App,
+    outbox: &Outbox,
+    checked: CheckedApp<'_>,
+    dry_run: bool,
 ) -> Result<()> {
+    let CheckedApp {
+        app,
+        previous,
+        ratings,
+    } = checked;
     let discord = get_discord(&ctx.repo, discord_cache, junction.server_id).await?;
-    let channel = serenity::ChannelId::new(discord.channel_id.try_into()?);
+    let mut alerts = Vec::new();
+
+    let old_price = previous.and_then(|o| o.price.as_ref());
+    if discord.price_change_alerts
+        && let Some(old_price) = old_price
+        && let Some(price) = &app.price_overview
+        && price.initial_price != old_price.initial_price
+    {
+        let alert = Alert::PriceChanged {
+            app: app.clone(),
+            old_price: old_price.clone(),
+        };
+        alerts.push(alert);
+    }
 
     if junction.coming_soon && !app.release_date.coming_soon {
-        channel
-            .send_message(
-                &ctx.http,
-                serenity::CreateMessage::new().embed(released_embed(app)),
-            )
-            .await?;
+        alerts.push(Alert::Released(app.clone()));
+    }
+    if junction.delisted {
+        alerts.push(Alert::Relisted(app.clone()));
+    }
+    let early_access = app.is_early_access();
+    if junction.early_access.is_some_and(|x| x != early_access) {
+        let alert = Alert::EarlyAccessChanged {
+            app: app.clone(),
+            entered: early_access,
+        };
+        alerts.push(alert);
+    }
+    if app.release_date.coming_soon
+        && !junction.release_date.is_empty()
+        && junction.release_date != app.release_date.date
+    {
+        let alert = Alert::ReleaseDateChanged {
+            app: app.clone(),
+            old_date: junction.release_date.clone(),
+        };
+        alerts.push(alert);
     }
 
+    // Either the discount meets the threshold or the discounted price meets the target.
     let threshold = junction.sale_threshold.unwrap_or(discord.sale_threshold);
-    let is_significant_discount = app
-        .price_overview
-        .as_ref()
-        .is_some_and(|p| p.discount_percent >= threshold);
+    let is_significant_discount = app.price_overview.as_ref().is_some_and(|p| {
+        p.discount_percent >= threshold
+            || (p.discount_percent > 0 && junction.target_price.is_some_and(|t| p.final_price <= t))
+    });
 
     if is_significant_discount && !junction.is_trailing_sale_day {
-        channel
-            .send_message(
-                &ctx.http,
-                serenity::CreateMessage::new().embed(sale_embed(app)),
-            )
-            .await?;
+        // Ratings are only fetched for guilds that filter on them, which also see them in alerts.
+        let ratings = if discord.has_rating_filters() {
+            let ratings = ratings.get_or_init(|| get_ratings(&ctx.steam, app.app_id));
+            ratings.await.clone()
+        } else {
+            steam::Ratings::default()
+        };
+        if discord.passes_filters(&ratings) {
+            let alert = Alert::Sale {
+                app: app.clone(),
+                ratings,
+                event: ctx
+                    .calendar
+                    .sale_at(chrono::Utc::now())
+                    .map(|e| e.name.clone()),
+            };
+            alerts.push(alert);
+        }
     }
 
     junction.coming_soon = app.release_date.coming_soon;
     junction.is_trailing_sale_day = is_significant_discount;
-    ctx.repo.junction.update_junction(&junction).await?;
+    junction.delisted = false;
+    junction.early_access = Some(early_access);
+    junction.release_date = app.release_date.date.clone();
+    if !dry_run {
+        // Queued first so the alerts aren't lost if the update succeeds but delivery doesn't.
+        alerts::queue(ctx, discord, &alerts).await?;
+        ctx.repo.junction.update_junction(&junction).await?;
+    }
+    for alert in alerts {
+        outbox.push(junction.server_id, alert);
+    }
+
+    Ok(())
+}
+
+/// Queues a delisted alert for each guild tracking the app that wasn't already
+/// alerted and marks their junctions as delisted, or untracks the app if the
+/// guild opted to. The app must be missing from [`MISSING_CHECKS_UNTIL_DELISTED`]
+/// checks in a row first. Nothing is updated if `dry_run`.
+async fn notify_delisted(
+    ctx: &framework::Data,
+    app_id: i32,
+    discord_cache: &OnceMap<i64, Arc<models::Discord>>,
+    outbox: &Outbox,
+    dry_run: bool,
+) -> Result<()> {
+    let app = if dry_run {
+        let app = ctx.repo.apps.get_app(app_id).await?;
+        app.map(|app| models::App {
+            missing_checks: app.missing_checks + 1,
+            ..app
+        })
+    } else {
+        ctx.repo.apps.add_missing_check(app_id).await?
+    };
+    let Some(app) = app else {
+        return Ok(());
+    };
+    if app.missing_checks < MISSING_CHECKS_UNTIL_DELISTED {
+        info!(
+            app_id,
+            missing_checks = app.missing_checks,
+            "Not alerting missing app as delisted yet"
+        );
+        return Ok(());
+    }
+
+    let mut junctions = ctx.repo.junction.get_junctions(app_id).await?;
+    while let Some(junction) = junctions.try_next().await? {
+        let guild_id = junction.server_id;
+        let res = delist_for_guild(ctx, junction, &app, discord_cache, outbox, dry_run).await;
+        if let Err(err) = res {
+            error!(
+                ?err,
+                guild_id, app_id, "Failed to notify guild of delisted app"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn delist_for_guild(
+    ctx: &framework::Data,
+    mut junction: models::Junction,
+    app: &models::App,
+    discord_cache: &OnceMap<i64, Arc<models::Discord>>,
+    outbox: &Outbox,
+    dry_run: bool,
+) -> Result<()> {
+    let discord = get_discord(&ctx.repo, discord_cache, junction.server_id).await?;
+
+    let alerts = if junction.delisted {
+        vec![]
+    } else {
+        vec![Alert::Delisted(app.clone())]
+    };
+    if !dry_run {
+        alerts::queue(ctx, discord, &alerts).await?;
+    }
+    for alert in alerts {
+        outbox.push(junction.server_id, alert);
+    }
+    if dry_run {
+        return Ok(());
+    }
+    if discord.auto_untrack_delisted {
+        ctx.repo
+            .junction
+            .remove_junction(junction.server_id, app.app_id)
+            .await?;
+    } else if !junction.delisted {
+        junction.delisted = true;
+        ctx.repo.junction.update_junction(&junction).await?;
+    }
 
     Ok(())
 }
@@ -230,86 +619,3 @@ async fn get_discord<'a>(
         .with_context(|| "Discord record doesn't exist but it's junction record does")?;
     Ok(discord_cache.insert(guild_id, |_| Arc::new(d)))
 }
-
-fn released_embed(app: &steam::App) -> serenity::CreateEmbed {
-    let title = format!("{} has released on Steam!", app.name);
-    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
-
-    let price = app
-        .price_overview
-        .as_ref()
-        .map(|p| p.final_formatted.clone())
-        .unwrap_or("Free".to_string());
-
-    let mut fields = vec![("Price", price, false)];
-    if !app.description.is_empty() {
-        fields.push(("Description", app.description.clone(), false));
-    }
-
-    serenity::CreateEmbed::new()
-        .title(title)
-        .url(url)
-        .image(app.header_image.clone())
-        .fields(fields)
-        .color(config::BRAND_DARK_COLOR)
-}
-
-fn sale_embed(app: &steam::App) -> serenity::CreateEmbed {
-    let price = app
-        .price_overview
-        .as_ref()
-        .expect("should have checked before called this fn");
-
-    let title = format!("{} is {}% off!", app.name, price.discount_percent);
-    let url = format!("https://store.steampowered.com/app/{}", app.app_id);
-
-    let mut fields = vec![
-        ("Original Price", price.initial_formatted.clone(), true),
-        ("Sale Price", price.final_formatted.clone(), true),
-    ];
-    if let Some(recs) = &app.recommendations {
-        fields.push(("Reviews", recs.total.to_string(), true));
-    }
-    if !app.description.is_empty() {
-        fields.push(("Description", app.description.clone(), false));
-    }
-
-    serenity::CreateEmbed::new()
-        .title(title)
-        .url(url)
-        .image(&app.header_image)
-        .fields(fields)
-        .color(sale_color(price.discount_percent))
-}
-
-fn sale_color(discount_percent: i32) -> u32 {
-    if discount_percent <= 5 {
-        0x0bff33
-    } else if discount_percent <= 10 {
-        0x44fdd2
-    } else if discount_percent <= 15 {
-        0x44fdfd
-    } else if discount_percent <= 20 {
-        0x44dbfd
-    } else if discount_percent <= 25 {
-        0x44b6fd
-    } else if discount_percent <= 30 {
-        0x448bfd
-    } else if discount_percent <= 35 {
-        0x445afd
-    } else if discount_percent <= 40 {
-        0x8544fd
-    } else if discount_percent <= 45 {
-        0xb044fd
-    } else if discount_percent <= 50 {
-        0xe144fd
-    } else if discount_percent <= 55 {
-        0xfd44de
-    } else if discount_percent <= 60 {
-        0xff23a7
-    } else if discount_percent <= 99 {
-        0xff0000
-    } else {
-        0xFFFFFF
-    }
-}
@@ -1,13 +1,16 @@
 //! This module provides [`run`] for starting the bot and internally
 //! sets the bot's configuration.
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use derivative::Derivative;
 use poise::serenity_prelude as serenity;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
-use crate::{Error, Result, StdResult, commands, events, repos, steam, util::PoiseData};
+use crate::{
+    Error, Result, StdResult, calendar, commands, config, events, lease, metrics, repos, scheduler,
+    shutdown, steam, util::PoiseData,
+};
 
 /// Custom data that is provided to all contexts.
 #[derive(Derivative)]
@@ -20,6 +23,10 @@ pub struct Data {
     /// A handle to the Steam client.
     #[derivative(Debug = "ignore")]
     pub steam: steam::Client,
+    /// Steam's seasonal sale events.
+    pub calendar: calendar::SaleCalendar,
+    /// Whether scheduled checks only report the alerts they would send.
+    pub dry_run: bool,
 }
 
 impl serenity::prelude::TypeMapKey for Data {
@@ -28,12 +35,20 @@ impl serenity::prelude::TypeMapKey for Data {
 
 pub type Context<'a> = poise::Context<'a, Arc<Data>, Error>;
 
-pub async fn run(token: &str, dev_guild: Option<u64>) -> Result<()> {
+/// Runs the bot. `owners` may use the owner commands in addition to the
+/// application's owner.
+pub async fn run(
+    token: &str,
+    dev_guild: Option<u64>,
+    owner_guild: Option<u64>,
+    owners: Vec<u64>,
+) -> Result<()> {
     let framework = poise::Framework::<Arc<Data>, Error>::builder()
         .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
                 info!("Setting up poise");
-                register_commands(ctx, &framework.options().commands, dev_guild).await?;
+                let commands = &framework.options().commands;
+                register_commands(ctx, commands, dev_guild, owner_guild).await?;
 
                 Ok(ctx.poise_data_unwrap().await)
             })
@@ -43,13 +58,32 @@ pub async fn run(token: &str, dev_guild: Option<u64>) -> Result<()> {
                 commands::help(),
                 commands::bind(),
                 commands::set_discount_threshold(),
+                commands::set_target_price(),
                 commands::list_apps(),
                 commands::clear_apps(),
                 commands::remove_apps(),
                 commands::add_apps(),
+                commands::add_dlc(),
                 commands::search(),
+                commands::deals(),
+                commands::app_info(),
+                commands::set_delivery_mode(),
+                commands::set_timezone(),
+                commands::set_quiet_hours(),
+                commands::set_sale_end_reminder(),
+                commands::set_alert_options(),
+                commands::owner(),
             ],
+            owners: owners.into_iter().map(serenity::UserId::new).collect(),
             command_check: Some(|ctx| Box::pin(command_check(ctx))),
+            pre_command: |ctx| {
+                Box::pin(async move {
+                    metrics::METRICS
+                        .command_invocations
+                        .with_label_values(&[&ctx.command().qualified_name])
+                        .inc();
+                })
+            },
             on_error: |err| Box::pin(on_error(err)),
             ..Default::default()
         })
@@ -62,24 +96,107 @@ pub async fn run(token: &str, dev_guild: Option<u64>) -> Result<()> {
         .event_handler(events::SerenityReady)
         .event_handler(events::GuildAvailable)
         .event_handler(events::RemovedFromGuild)
+        .event_handler(events::GatewayStatus)
         .await;
 
+    let mut client = client?;
+    let shard_manager = client.shard_manager.clone();
+    let store = client.data.clone();
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    tokio::spawn(async move {
+        shutdown::wait_for_signal().await;
+        shutdown_gracefully(shard_manager, store).await;
+        done_tx.send(()).ok();
+    });
+
     info!("Starting framework");
-    Ok(client?.start().await?)
+    match config::get().discord.sharding {
+        config::Sharding::Single => client.start().await?,
+        config::Sharding::Auto => client.start_autosharded().await?,
+        // Serenity's range end is inclusive.
+        config::Sharding::Range { first, last, total } => {
+            client.start_shard_range(first..last, total).await?
+        }
+    }
+    if shutdown::is_shutting_down() {
+        done_rx.await.ok();
+    }
+
+    Ok(())
+}
+
+/// Lets running jobs stop where they can and waits for them, releases the lease
+/// on scheduled jobs, then disconnects from Discord and the database within the
+/// configured timeout.
+async fn shutdown_gracefully(
+    shard_manager: Arc<serenity::ShardManager>,
+    store: Arc<serenity::prelude::RwLock<serenity::prelude::TypeMap>>,
+) {
+    info!("Shutting down...");
+    let timeout = Duration::from_secs(config::get().shutdown.timeout_secs);
+    let deadline = tokio::time::Instant::now() + timeout;
+
+    let jobs = async {
+        tokio::join!(
+            events::wait_for_check(),
+            scheduler::SCHEDULER.wait_for_jobs()
+        )
+    };
+    if tokio::time::timeout_at(deadline, jobs).await.is_err() {
+        warn!("Timed out waiting for running jobs to stop");
+    }
+
+    let data = store.read().await.get::<Data>().cloned();
+    if let Some(data) = &data
+        && tokio::time::timeout_at(deadline, lease::LEASE.release(&data.repo))
+            .await
+            .is_err()
+    {
+        warn!("Timed out releasing the lease");
+    }
+    shard_manager.shutdown_all().await;
+
+    if let Some(data) = data
+        && tokio::time::timeout_at(deadline, data.repo.shutdown())
+            .await
+            .is_err()
+    {
+        warn!("Timed out closing database connections");
+    }
+    info!("Shut down");
 }
 
 async fn register_commands(
     ctx: &serenity::Context,
     commands: &[poise::Command<Arc<Data>, Error>],
     dev_guild: Option<u64>,
+    owner_guild: Option<u64>,
 ) -> StdResult<(), serenity::Error> {
-    match dev_guild {
-        Some(guild_id) => {
+    match (dev_guild, owner_guild) {
+        (Some(guild_id), _) => {
             info!("Registering commands in development guild {}", guild_id);
             poise::builtins::register_in_guild(ctx, commands, serenity::GuildId::new(guild_id))
                 .await?;
         }
-        None => {
+        (None, Some(guild_id)) => {
+            let (owner_commands, commands): (Vec<_>, Vec<_>) =
+                commands.iter().partition(|command| command.owners_only);
+            // The bot has no context menu commands, so only slash commands are created.
+            let create = |commands: Vec<&poise::Command<_, _>>| {
+                commands
+                    .into_iter()
+                    .filter_map(|command| command.create_as_slash_command())
+                    .collect()
+            };
+
+            info!("Registering commands globally");
+            serenity::Command::set_global_commands(ctx, create(commands)).await?;
+            info!("Registering owner commands in guild {}", guild_id);
+            serenity::GuildId::new(guild_id)
+                .set_commands(ctx, create(owner_commands))
+                .await?;
+        }
+        (None, None) => {
             info!("Registering commands globally");
             poise::builtins::register_globally(ctx, commands).await?;
         }
@@ -89,7 +206,13 @@ async fn register_commands(
 }
 
 async fn command_check(ctx: Context<'_>) -> Result<bool> {
-    if ctx.guild_id().is_some() {
+    if shutdown::is_shutting_down() {
+        ctx.say("The bot is restarting. Please try again in a minute.")
+            .await?;
+        return Ok(false);
+    }
+    // Owner commands also work in DMs. Poise already rejects non-owners.
+    if ctx.command().owners_only || ctx.guild_id().is_some() {
         return Ok(true);
     }
     ctx.say("Commands must be used in a server").await?;
@@ -97,6 +220,13 @@ async fn command_check(ctx: Context<'_>) -> Result<bool> {
 }
 
 pub async fn on_error(err: poise::FrameworkError<'_, Arc<Data>, Error>) {
+    if let Some(ctx) = err.ctx() {
+        metrics::METRICS
+            .command_errors
+            .with_label_values(&[&ctx.command().qualified_name])
+            .inc();
+    }
+
     if let poise::FrameworkError::CooldownHit {
         remaining_cooldown,
         ctx,
@@ -0,0 +1,134 @@
+//! This module provides the bot's [`HEALTH`] as reported by the `/healthz`
+//! and `/readyz` endpoints.
+
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex, OnceLock},
+    time::Duration,
+};
+
+use crate::repos;
+
+/// Checks run daily, so a check older than this means the loop is stuck or failing.
+const MAX_CHECK_AGE: chrono::TimeDelta = chrono::TimeDelta::hours(26);
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub static HEALTH: LazyLock<Health> = LazyLock::new(Health::new);
+
+pub struct Health {
+    /// Whether each of the process's shards is connected to the gateway.
+    shards: Mutex<HashMap<u32, bool>>,
+    /// Since when this process has run checks, if it does. Other processes
+    /// don't need recent checks.
+    runs_checks_since: Mutex<Option<chrono::DateTime<chrono::Utc>>>,
+    last_check: Mutex<Option<chrono::DateTime<chrono::Utc>>>,
+    /// Set once the framework data is created.
+    repo: OnceLock<repos::Repo>,
+}
+
+/// A snapshot of the bot's health.
+#[derive(Debug, serde::Serialize)]
+pub struct Report {
+    pub gateway_connected: bool,
+    pub database_reachable: bool,
+    /// Seconds since the last successful check, if one has run.
+    pub last_check_age_secs: Option<i64>,
+    pub check_fresh: bool,
+}
+
+impl Report {
+    /// Whether the process is alive. Unhealthy bots should be restarted, so this
+    /// ignores the database and checks, which a restart wouldn't fix.
+    pub fn is_healthy(&self) -> bool {
+        self.gateway_connected
+    }
+
+    /// Whether the bot is able to serve commands and, if it runs them, checks.
+    pub fn is_ready(&self) -> bool {
+        self.gateway_connected && self.database_reachable && self.check_fresh
+    }
+}
+
+impl Health {
+    fn new() -> Self {
+        Self {
+            shards: Mutex::new(HashMap::new()),
+            runs_checks_since: Mutex::new(None),
+            last_check: Mutex::new(None),
+            repo: OnceLock::new(),
+        }
+    }
+
+    pub fn set_shard_connected(&self, shard_id: u32, connected: bool) {
+        self.shards
+            .lock()
+            .expect("health lock shouldn't be poisoned")
+            .insert(shard_id, connected);
+    }
+
+    pub fn set_runs_checks(&self, runs_checks: bool) {
+        let mut since = self
+            .runs_checks_since
+            .lock()
+            .expect("health lock shouldn't be poisoned");
+        if !runs_checks {
+            *since = None;
+        } else if since.is_none() {
+            *since = Some(chrono::Utc::now());
+        }
+    }
+
+    fn gateway_connected(&self) -> bool {
+        let shards = self
+            .shards
+            .lock()
+            .expect("health lock shouldn't be poisoned");
+        !shards.is_empty() && shards.values().all(|&connected| connected)
+    }
+
+    pub fn set_repo(&self, repo: repos::Repo) {
+        self.repo.set(repo).ok();
+    }
+
+    pub fn record_check(&self, at: chrono::DateTime<chrono::Utc>) {
+        *self
+            .last_check
+            .lock()
+            .expect("health lock shouldn't be poisoned") = Some(at);
+    }
+
+    /// When the last successful check finished, if one has.
+    pub fn last_check(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        *self
+            .last_check
+            .lock()
+            .expect("health lock shouldn't be poisoned")
+    }
+
+    pub async fn report(&self) -> Report {
+        let now = chrono::Utc::now();
+        let last_check = self.last_check();
+        let runs_checks_since = *self
+            .runs_checks_since
+            .lock()
+            .expect("health lock shouldn't be poisoned");
+        // After starting to run checks, wait as long as a check would take to come around.
+        let check_fresh = runs_checks_since.is_none_or(|since| {
+            now - last_check.map_or(since, |at| at.max(since)) <= MAX_CHECK_AGE
+        });
+
+        let database_reachable = match self.repo.get() {
+            Some(repo) => tokio::time::timeout(PING_TIMEOUT, repo.ping())
+                .await
+                .is_ok_and(|res| res.is_ok()),
+            None => false,
+        };
+
+        Report {
+            gateway_connected: self.gateway_connected(),
+            database_reachable,
+            last_check_age_secs: last_check.map(|at| (now - at).num_seconds()),
+            check_fresh,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+//! This module provides the HTTP server exposing the bot's [`metrics`] and
+//! [`health`], and [`probe`] for checking a running bot's health.
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, bail};
+use axum::{
+    Json, Router,
+    http::{StatusCode, header},
+    response::IntoResponse,
+    routing::get,
+};
+use tracing::info;
+
+use crate::{Result, health, metrics, shutdown};
+
+/// Serves the HTTP endpoints on `addr` until an error occurs.
+pub async fn serve(addr: &str) -> Result<()> {
+    let app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/healthz", get(healthz_handler))
+        .route("/readyz", get(readyz_handler));
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("Serving HTTP on {addr}");
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown::SHUTDOWN.cancelled())
+        .await?;
+
+    Ok(())
+}
+
+/// Requests `/healthz` from the bot serving on `addr`, failing if it's unhealthy.
+pub async fn probe(addr: &str) -> Result<()> {
+    let mut addr: SocketAddr = addr.parse().with_context(|| "Parsing http.addr")?;
+    if addr.ip().is_unspecified() {
+        addr.set_ip([127, 0, 0, 1].into());
+    }
+
+    let res = reqwest::Client::new()
+        .get(format!("http://{addr}/healthz"))
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+    let status = res.status();
+    let body = res.text().await?;
+    if !status.is_success() {
+        bail!("Unhealthy ({status}): {body}");
+    }
+    info!(body, "Healthy");
+
+    Ok(())
+}
+
+async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)],
+        metrics::METRICS.encode(),
+    )
+}
+
+async fn healthz_handler() -> impl IntoResponse {
+    let report = health::HEALTH.report().await;
+    (status_code(report.is_healthy()), Json(report))
+}
+
+async fn readyz_handler() -> impl IntoResponse {
+    let report = health::HEALTH.report().await;
+    (status_code(report.is_ready()), Json(report))
+}
+
+fn status_code(ok: bool) -> StatusCode {
+    if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
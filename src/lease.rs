@@ -0,0 +1,139 @@
+//! This module provides the [`LEASE`] on scheduled jobs, so only one of the
+//! bot's processes runs them when several are deployed. The others serve
+//! commands and take over if the holder stops renewing the lease.
+
+use std::{
+    sync::{LazyLock, Mutex},
+    time::Duration,
+};
+
+use mongodb::bson;
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+
+use crate::{config, health, repos};
+
+const SCHEDULER_LEASE: &str = "scheduler";
+
+pub static LEASE: LazyLock<Lease> = LazyLock::new(Lease::new);
+
+pub struct Lease {
+    /// Identifies this process as the lease's holder.
+    holder: String,
+    /// When the lease expires, if this process holds it.
+    held_until: Mutex<Option<Instant>>,
+    /// Held while the lease is being renewed or released. Set once released so
+    /// it isn't renewed again.
+    released: tokio::sync::Mutex<bool>,
+    /// Notified when this process acquires the lease.
+    acquired: tokio::sync::Notify,
+}
+
+impl Lease {
+    fn new() -> Self {
+        let host = std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string());
+        Self {
+            holder: format!("{host}-{}", bson::oid::ObjectId::new()),
+            held_until: Mutex::new(None),
+            released: tokio::sync::Mutex::new(false),
+            acquired: tokio::sync::Notify::new(),
+        }
+    }
+
+    pub fn holder(&self) -> &str {
+        &self.holder
+    }
+
+    /// Whether this process holds the lease and so should run scheduled jobs.
+    pub fn is_held(&self) -> bool {
+        self.held_until()
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Waits until this process next acquires the lease. Only one task should wait.
+    pub async fn acquired(&self) {
+        self.acquired.notified().await;
+    }
+
+    fn held_until(&self) -> Option<Instant> {
+        *self
+            .held_until
+            .lock()
+            .expect("lease lock shouldn't be poisoned")
+    }
+
+    fn set_held_until(&self, until: Option<Instant>) {
+        *self
+            .held_until
+            .lock()
+            .expect("lease lock shouldn't be poisoned") = until;
+        health::HEALTH.set_runs_checks(until.is_some());
+    }
+
+    /// Acquires or renews the lease every third of its TTL until it's released.
+    pub async fn keep_renewed(&self, repo: repos::Repo) {
+        let ttl = Duration::from_secs(config::get().lease.ttl_secs);
+        let mut interval = tokio::time::interval(ttl / 3);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+            let released = self.released.lock().await;
+            if *released {
+                break;
+            }
+
+            // Measured before renewing so the lease never outlives the database's.
+            let renewed_at = Instant::now();
+            match repo
+                .leases
+                .try_acquire(SCHEDULER_LEASE, &self.holder, ttl)
+                .await
+            {
+                Ok(true) => {
+                    let acquired = !self.is_held();
+                    self.set_held_until(Some(renewed_at + ttl));
+                    if acquired {
+                        info!(holder = self.holder, "Acquired lease on scheduled jobs");
+                        self.acquired.notify_one();
+                    }
+                }
+                Ok(false) => {
+                    if self.is_held() {
+                        warn!(holder = self.holder, "Lost lease on scheduled jobs");
+                    }
+                    self.set_held_until(None);
+                }
+                // The lease lapses on its own if renewing keeps failing.
+                Err(err) => error!(?err, "Failed to renew lease on scheduled jobs"),
+            }
+        }
+    }
+
+    /// Stops renewing the lease and releases it so another process can take
+    /// over without waiting for it to expire.
+    pub async fn release(&self, repo: &repos::Repo) {
+        let mut released = self.released.lock().await;
+        *released = true;
+        if self.held_until().is_none() {
+            return;
+        }
+
+        self.set_held_until(None);
+        match repo.leases.release(SCHEDULER_LEASE, &self.holder).await {
+            Ok(_) => info!("Released lease on scheduled jobs"),
+            Err(err) => error!(?err, "Failed to release lease on scheduled jobs"),
+        }
+    }
+
+    /// Gets which process holds the lease, if any.
+    pub async fn current_holder(
+        &self,
+        repo: &repos::Repo,
+    ) -> mongodb::error::Result<Option<String>> {
+        let lease = repo.leases.get_lease(SCHEDULER_LEASE).await?;
+        Ok(lease
+            .filter(|lease| lease.expires_at > bson::DateTime::now())
+            .map(|lease| lease.holder))
+    }
+}
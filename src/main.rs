@@ -1,15 +1,26 @@
-use tracing_subscriber::EnvFilter;
-
 use crate::util::ResLog;
 
+mod alerts;
+mod bindings;
+mod calendar;
 mod commands;
 mod config;
 mod database;
+mod digest;
+mod embeds;
 mod events;
 mod framework;
+mod health;
+mod http;
+mod lease;
+mod metrics;
 mod models;
+mod reminders;
 mod repos;
+mod scheduler;
+mod shutdown;
 mod steam;
+mod telemetry;
 mod util;
 
 type StdResult<T, E> = std::result::Result<T, E>;
@@ -18,19 +29,44 @@ type Error = anyhow::Error;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::from_default_env())
-        .init();
-    dotenvy::dotenv().twarn().ok();
-
-    let token: String = util::env_var("DISCORD_TOKEN")?;
-    let dev_guild: Option<u64> = match util::env_var("DISCORD_DEVGUILDID") {
-        Ok(x) => Some(x),
-        Err(util::EnvVarError::InvalidOrMissingKey { .. }) => None,
-        Err(err) => Err(err)?,
-    };
-
-    framework::run(&token, dev_guild).await?;
+    // Loaded first so the env file can configure the bot.
+    let dotenv = dotenvy::dotenv();
+    let config = config::init(config::Config::load()?);
+    let telemetry = telemetry::init(config.log.format)?;
+    dotenv.twarn().ok();
+
+    let res = run(config).await;
+    telemetry.shutdown();
+    res
+}
+
+async fn run(config: &'static config::Config) -> Result<()> {
+    // Lets the scratch image, which has no shell or curl, check its own health.
+    if std::env::args().any(|arg| arg == "--healthcheck") {
+        if config.http.addr.is_empty() {
+            anyhow::bail!("Can't check health since the HTTP server is disabled");
+        }
+        return http::probe(&config.http.addr).await;
+    }
+
+    if config.http.addr.is_empty() {
+        tracing::info!("HTTP server is disabled");
+    } else {
+        tokio::spawn(async move {
+            http::serve(&config.http.addr)
+                .await
+                .inspect_err(|err| tracing::error!(?err, "HTTP server stopped"))
+        });
+    }
+
+    let discord = &config.discord;
+    framework::run(
+        &discord.token,
+        discord.dev_guild_id,
+        discord.owner_guild_id,
+        discord.owner_ids.clone(),
+    )
+    .await?;
 
     Ok(())
 }
@@ -0,0 +1,126 @@
+//! This module provides Prometheus [`METRICS`] about the bot's operation.
+
+use std::sync::LazyLock;
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    /// Apps successfully fetched during checks.
+    pub apps_checked: IntCounter,
+    /// Steam fetch errors by [`crate::steam::FetchError`] variant.
+    pub fetch_errors: IntCounterVec,
+    /// Times checking backed off after being rate-limited by Steam.
+    pub rate_limit_backoffs: IntCounter,
+    /// Alerts sent to guilds by kind.
+    pub alerts_sent: IntCounterVec,
+    /// Duration of checking all apps.
+    pub check_duration: Histogram,
+    /// Command invocations by command.
+    pub command_invocations: IntCounterVec,
+    /// Command errors by command.
+    pub command_errors: IntCounterVec,
+    /// Guilds the bot is in.
+    pub guilds: IntGauge,
+    /// Scheduled job runs by job and [`crate::models::JobStatus`].
+    pub job_runs: IntCounterVec,
+    /// Guilds whose bound channel was missing or inaccessible as of the last check.
+    pub broken_bindings: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("steamsale".to_string()), None)
+            .expect("prefix should be valid");
+
+        let apps_checked =
+            IntCounter::new("apps_checked_total", "Apps fetched during checks").unwrap();
+        let fetch_errors = IntCounterVec::new(
+            Opts::new("steam_fetch_errors_total", "Steam fetch errors"),
+            &["variant"],
+        )
+        .unwrap();
+        let rate_limit_backoffs = IntCounter::new(
+            "steam_rate_limit_backoffs_total",
+            "Back offs after being rate-limited by Steam",
+        )
+        .unwrap();
+        let alerts_sent = IntCounterVec::new(
+            Opts::new("alerts_sent_total", "Alerts sent to guilds"),
+            &["kind"],
+        )
+        .unwrap();
+        // A check can take hours when rate-limited.
+        let check_duration = Histogram::with_opts(
+            HistogramOpts::new("check_duration_seconds", "Duration of checking all apps")
+                .buckets(prometheus::exponential_buckets(30.0, 2.0, 10).unwrap()),
+        )
+        .unwrap();
+        let command_invocations = IntCounterVec::new(
+            Opts::new("command_invocations_total", "Command invocations"),
+            &["command"],
+        )
+        .unwrap();
+        let command_errors = IntCounterVec::new(
+            Opts::new("command_errors_total", "Command errors"),
+            &["command"],
+        )
+        .unwrap();
+        let guilds = IntGauge::new("guilds", "Guilds the bot is in").unwrap();
+        let job_runs = IntCounterVec::new(
+            Opts::new("job_runs_total", "Scheduled job runs"),
+            &["job", "status"],
+        )
+        .unwrap();
+        let broken_bindings = IntGauge::new(
+            "broken_bindings",
+            "Guilds whose bound channel is missing or inaccessible",
+        )
+        .unwrap();
+
+        registry.register(Box::new(apps_checked.clone())).unwrap();
+        registry.register(Box::new(fetch_errors.clone())).unwrap();
+        registry
+            .register(Box::new(rate_limit_backoffs.clone()))
+            .unwrap();
+        registry.register(Box::new(alerts_sent.clone())).unwrap();
+        registry.register(Box::new(check_duration.clone())).unwrap();
+        registry
+            .register(Box::new(command_invocations.clone()))
+            .unwrap();
+        registry.register(Box::new(command_errors.clone())).unwrap();
+        registry.register(Box::new(guilds.clone())).unwrap();
+        registry.register(Box::new(job_runs.clone())).unwrap();
+        registry
+            .register(Box::new(broken_bindings.clone()))
+            .unwrap();
+
+        Self {
+            registry,
+            apps_checked,
+            fetch_errors,
+            rate_limit_backoffs,
+            alerts_sent,
+            check_duration,
+            command_invocations,
+            command_errors,
+            guilds,
+            job_runs,
+            broken_bindings,
+        }
+    }
+
+    /// Encodes all metrics in the Prometheus text format.
+    pub fn encode(&self) -> String {
+        let mut buf = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buf)
+            .expect("encoding to a Vec shouldn't fail");
+        String::from_utf8(buf).expect("text format should be utf8")
+    }
+}
@@ -3,7 +3,7 @@
 use derivative::Derivative;
 use mongodb::bson;
 
-use crate::steam;
+use crate::{alerts, steam};
 
 #[derive(Debug, Clone, PartialEq, Eq, Derivative, serde::Deserialize, serde::Serialize)]
 #[derivative(Default)]
@@ -14,6 +14,100 @@ pub struct Discord {
     #[derivative(Default(value = "1"))]
     pub sale_threshold: i32,
     pub server_id: i64,
+    #[serde(default)]
+    pub delivery_mode: DeliveryMode,
+    /// Timezone that scheduled deliveries, such as the weekly digest, follow.
+    #[serde(default)]
+    pub timezone: chrono_tz::Tz,
+    /// When the last weekly digest was sent.
+    #[serde(default)]
+    pub last_digest_at: Option<bson::DateTime>,
+    /// Hours in the guild's timezone during which alerts are queued instead of sent.
+    #[serde(default)]
+    pub quiet_hours: Option<QuietHours>,
+    /// How many hours before a sale ends to send a reminder. `None` if disabled.
+    #[serde(default)]
+    pub sale_end_reminder_hours: Option<i32>,
+    /// Whether to alert when a tracked app's base price changes.
+    #[serde(default)]
+    pub price_change_alerts: bool,
+    /// Whether to untrack apps once they're alerted as delisted.
+    #[serde(default)]
+    pub auto_untrack_delisted: bool,
+    /// Minimum percentage of positive reviews for a sale alert. `None` if disabled.
+    #[serde(default)]
+    pub min_review_percent: Option<i32>,
+    /// Whether sale alerts require Steam Deck Verified or Playable status.
+    #[serde(default)]
+    pub require_deck_playable: bool,
+    /// Whether to announce the start of Steam's seasonal sale events.
+    #[serde(default)]
+    pub sale_event_announcements: bool,
+    /// Start of the sale event that was last announced.
+    #[serde(default)]
+    pub announced_event_start: Option<bson::DateTime>,
+}
+
+impl Discord {
+    /// Whether `now` falls within the guild's quiet hours.
+    pub fn is_quiet_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        use chrono::Timelike;
+
+        self.quiet_hours
+            .is_some_and(|q| q.contains(now.with_timezone(&self.timezone).hour()))
+    }
+
+    /// Whether the guild filters sale alerts by the app's [`steam::Ratings`].
+    pub fn has_rating_filters(&self) -> bool {
+        self.min_review_percent.is_some() || self.require_deck_playable
+    }
+
+    /// Whether the app's ratings pass the guild's sale alert filters. Ratings that
+    /// couldn't be fetched pass, but apps untested on the Steam Deck don't.
+    pub fn passes_filters(&self, ratings: &steam::Ratings) -> bool {
+        let passes_reviews = match (self.min_review_percent, &ratings.reviews) {
+            (Some(min), Some(reviews)) => reviews.positive_percent().is_none_or(|p| p >= min),
+            _ => true,
+        };
+        let passes_deck =
+            !self.require_deck_playable || ratings.deck.is_none_or(|deck| deck.is_playable());
+
+        passes_reviews && passes_deck
+    }
+}
+
+/// A daily window from `start` up to, but excluding, `end`. Wraps past midnight
+/// when `end` is before `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct QuietHours {
+    pub start: u32,
+    pub end: u32,
+}
+
+impl QuietHours {
+    pub fn contains(&self, hour: u32) -> bool {
+        if self.start <= self.end {
+            (self.start..self.end).contains(&hour)
+        } else {
+            hour >= self.start || hour < self.end
+        }
+    }
+}
+
+/// How a guild's alerts from a check are sent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeliveryMode {
+    /// One embed per alert.
+    #[default]
+    Individual,
+    /// Alerts are grouped into summary embeds.
+    Digest,
+    /// Individual alerts unless there are more than `threshold` alerts.
+    Auto { threshold: i32 },
+    /// No alerts per check. Instead, a weekly digest is sent at `hour` on `weekday`
+    /// in the guild's timezone.
+    Weekly { weekday: chrono::Weekday, hour: u32 },
 }
 
 #[derive(
@@ -28,6 +122,23 @@ pub struct Junction {
     pub coming_soon: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sale_threshold: Option<i32>,
+    /// Alert when the discounted price is at or below this, in the currency's
+    /// minor units, e.g. cents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_price: Option<i64>,
+    /// Whether the app was missing from the store as of the last check.
+    #[serde(default)]
+    pub delisted: bool,
+    /// Whether the app was in Early Access as of the last check. `None` if not
+    /// checked yet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub early_access: Option<bool>,
+    /// Release date text as of the last check. Empty if not checked yet.
+    #[serde(default)]
+    pub release_date: String,
+    /// Discount end of the sale the guild was last reminded about.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reminded_discount_end: Option<bson::DateTime>,
 }
 
 #[derive(
@@ -38,6 +149,30 @@ pub struct App {
     pub id: bson::oid::ObjectId,
     pub app_id: i32,
     pub app_name: String,
+    /// Last-known price. `None` if the app is free or unpriced.
+    #[serde(default)]
+    pub price: Option<Price>,
+    #[serde(default)]
+    pub header_image: String,
+    /// Release date text as displayed by Steam, e.g. "Aug 14, 2026" or "Coming soon".
+    #[serde(default)]
+    pub release_date: String,
+    /// Total number of reviews.
+    #[serde(default)]
+    pub review_count: Option<i64>,
+    #[serde(default)]
+    pub developers: Vec<String>,
+    #[serde(default)]
+    pub publishers: Vec<String>,
+    /// Name of the base game if the app is DLC.
+    #[serde(default)]
+    pub fullgame_name: Option<String>,
+    /// When this metadata was last refreshed from Steam.
+    #[serde(default)]
+    pub updated_at: Option<bson::DateTime>,
+    /// Checks in a row that didn't find the app on Steam.
+    #[serde(default)]
+    pub missing_checks: i32,
 }
 
 impl From<steam::App> for App {
@@ -46,13 +181,288 @@ impl From<steam::App> for App {
             id: Default::default(),
             app_id: app.app_id,
             app_name: app.name,
+            price: app.price_overview.map(Into::into),
+            header_image: app.header_image,
+            release_date: app.release_date.date,
+            review_count: app.recommendations.map(|r| r.total.into()),
+            developers: app.developers,
+            publishers: app.publishers,
+            fullgame_name: app.fullgame.map(|g| g.name),
+            updated_at: Some(bson::DateTime::now()),
+            missing_checks: 0,
         }
     }
 }
 
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Price {
+    pub discount_percent: i32,
+    /// Price before discount in the currency's minor units, e.g. cents.
+    #[serde(default)]
+    pub initial_price: i64,
+    /// Price after discount in the currency's minor units, e.g. cents.
+    #[serde(default)]
+    pub final_price: i64,
+    pub initial_formatted: String,
+    pub final_formatted: String,
+    /// When the discount ends, if known.
+    #[serde(default)]
+    pub discount_end: Option<bson::DateTime>,
+}
+
+impl From<steam::PriceOverview> for Price {
+    fn from(price: steam::PriceOverview) -> Self {
+        Self {
+            discount_percent: price.discount_percent,
+            initial_price: price.initial_price,
+            final_price: price.final_price,
+            initial_formatted: price.initial_formatted,
+            final_formatted: price.final_formatted,
+            discount_end: price
+                .discount_end_date
+                .map(|ts| bson::DateTime::from_millis(ts * 1000)),
+        }
+    }
+}
+
+/// A snapshot of an app's state taken during a check.
+#[derive(Debug, Clone, PartialEq, Eq, Derivative, serde::Deserialize, serde::Serialize)]
+#[derivative(Default)]
+pub struct Observation {
+    #[serde(rename = "_id")]
+    pub id: bson::oid::ObjectId,
+    pub app_id: i32,
+    #[derivative(Default(value = "bson::DateTime::now()"))]
+    pub observed_at: bson::DateTime,
+    pub coming_soon: bool,
+    pub price: Option<Price>,
+}
+
+impl From<&steam::App> for Observation {
+    fn from(app: &steam::App) -> Self {
+        Self {
+            id: Default::default(),
+            app_id: app.app_id,
+            observed_at: bson::DateTime::now(),
+            coming_soon: app.release_date.coming_soon,
+            price: app.price_overview.clone().map(Into::into),
+        }
+    }
+}
+
+/// Aggregate of an app's observations within some period.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ObservationSummary {
+    #[serde(rename = "_id")]
+    pub app_id: i32,
+    /// Lowest observed final price. `None` if the app was never priced.
+    pub lowest_price: Option<i64>,
+    /// Whether the app was coming soon as of the latest observation.
+    pub coming_soon: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AppListing {
     pub app_id: i32,
     pub app_name: String,
     pub sale_threshold: Option<i32>,
+    /// Last-known discount of the app. `None` if the app is free or unpriced.
+    pub discount_percent: Option<i32>,
+    /// When the app started being tracked by the guild.
+    pub added_at: bson::DateTime,
+    /// Release date text of the app if it's coming soon.
+    pub upcoming_release_date: Option<String>,
+}
+
+/// An app along with how many guilds track it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedApp {
+    pub app_id: i32,
+    pub app_name: String,
+    pub trackers: i32,
+}
+
+/// A lease on scheduled work, held by one process at a time until it expires.
+#[derive(Debug, Clone, PartialEq, Eq, Derivative, serde::Deserialize, serde::Serialize)]
+#[derivative(Default)]
+pub struct Lease {
+    #[serde(rename = "_id")]
+    pub name: String,
+    pub holder: String,
+    #[derivative(Default(value = "bson::DateTime::now()"))]
+    pub expires_at: bson::DateTime,
+}
+
+/// The last run of a scheduled job.
+#[derive(Debug, Clone, PartialEq, Eq, Derivative, serde::Deserialize, serde::Serialize)]
+#[derivative(Default)]
+pub struct JobRun {
+    #[serde(rename = "_id")]
+    pub job: String,
+    /// The process that ran the job.
+    pub holder: String,
+    pub trigger: JobTrigger,
+    pub status: JobStatus,
+    #[derivative(Default(value = "bson::DateTime::now()"))]
+    pub started_at: bson::DateTime,
+    pub finished_at: Option<bson::DateTime>,
+    pub error: Option<String>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum JobTrigger {
+    /// Ran on the job's schedule.
+    #[default]
+    Scheduled,
+    /// Ran by an owner.
+    Manual,
+    /// Ran to finish a run that a previous lease holder was interrupted during.
+    Resumed,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum JobStatus {
+    #[default]
+    Running,
+    Succeeded,
+    Failed,
+    TimedOut,
+    /// Stopped early, such as for shutdown.
+    Interrupted,
+}
+
+/// How far a check got before it was interrupted, so it can be resumed.
+#[derive(Debug, Clone, PartialEq, Eq, Derivative, serde::Deserialize, serde::Serialize)]
+#[derivative(Default)]
+pub struct CheckCursor {
+    #[serde(rename = "_id")]
+    pub name: String,
+    /// When the interrupted check started. Resuming keeps it.
+    #[derivative(Default(value = "bson::DateTime::now()"))]
+    pub started_at: bson::DateTime,
+    /// The last app checked. Apps are checked in ascending order of ID.
+    pub last_app_id: i32,
+}
+
+/// An alert held back during a guild's quiet hours.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct QueuedAlert {
+    #[serde(rename = "_id")]
+    pub id: bson::oid::ObjectId,
+    pub server_id: i64,
+    pub alert: alerts::Alert,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::{
+        models::{Discord, QuietHours},
+        steam::{DeckCompatibility, Ratings, ReviewSummary},
+    };
+
+    #[rstest]
+    #[case::inside(QuietHours { start: 9, end: 17 }, 12, true)]
+    #[case::at_start(QuietHours { start: 9, end: 17 }, 9, true)]
+    #[case::at_end(QuietHours { start: 9, end: 17 }, 17, false)]
+    #[case::before(QuietHours { start: 9, end: 17 }, 8, false)]
+    #[case::wraps_before_midnight(QuietHours { start: 22, end: 7 }, 23, true)]
+    #[case::wraps_after_midnight(QuietHours { start: 22, end: 7 }, 3, true)]
+    #[case::wraps_at_end(QuietHours { start: 22, end: 7 }, 7, false)]
+    #[case::wraps_outside(QuietHours { start: 22, end: 7 }, 12, false)]
+    #[case::empty(QuietHours { start: 9, end: 9 }, 9, false)]
+    fn quiet_hours_contains_wraps_past_midnight(
+        #[case] quiet_hours: QuietHours,
+        #[case] hour: u32,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(expected, quiet_hours.contains(hour));
+    }
+
+    /// Quiet from 22:00 to 07:00 in New York, which is UTC-4 in October.
+    #[rstest]
+    #[case::quiet_in_local_time("2026-10-19T03:00:00Z", true)]
+    #[case::awake_in_local_time("2026-10-19T12:00:00Z", false)]
+    #[case::quiet_in_utc_only("2026-10-19T23:00:00Z", false)]
+    #[case::ends_in_local_time("2026-10-19T11:00:00Z", false)]
+    fn is_quiet_at_follows_timezone(#[case] now: &str, #[case] expected: bool) {
+        let discord = Discord {
+            timezone: chrono_tz::America::New_York,
+            quiet_hours: Some(QuietHours { start: 22, end: 7 }),
+            ..Default::default()
+        };
+
+        assert_eq!(expected, discord.is_quiet_at(now.parse().unwrap()));
+    }
+
+    #[test]
+    fn is_quiet_at_is_false_without_quiet_hours() {
+        let discord = Discord::default();
+
+        assert!(!discord.is_quiet_at(chrono::Utc::now()));
+    }
+
+    #[rstest]
+    #[case::no_filters(None, false, Some((0, 10)), Some(DeckCompatibility::Unsupported), true)]
+    #[case::missing_reviews(Some(80), false, None, None, true)]
+    #[case::no_reviews_yet(Some(80), false, Some((0, 0)), None, true)]
+    #[case::reviews_at_threshold(Some(80), false, Some((80, 20)), None, true)]
+    #[case::reviews_below_threshold(Some(80), false, Some((79, 21)), None, false)]
+    #[case::missing_deck(None, true, None, None, true)]
+    #[case::deck_verified(None, true, None, Some(DeckCompatibility::Verified), true)]
+    #[case::deck_playable(None, true, None, Some(DeckCompatibility::Playable), true)]
+    #[case::deck_unsupported(None, true, None, Some(DeckCompatibility::Unsupported), false)]
+    #[case::deck_untested(None, true, None, Some(DeckCompatibility::Unknown), false)]
+    #[case::both_pass(Some(80), true, Some((90, 10)), Some(DeckCompatibility::Verified), true)]
+    #[case::only_reviews_pass(Some(80), true, Some((90, 10)), Some(DeckCompatibility::Unsupported), false)]
+    #[case::only_deck_passes(Some(80), true, Some((10, 90)), Some(DeckCompatibility::Verified), false)]
+    fn passes_filters_checks_each_rating(
+        #[case] min_review_percent: Option<i32>,
+        #[case] require_deck_playable: bool,
+        #[case] reviews: Option<(u32, u32)>,
+        #[case] deck: Option<DeckCompatibility>,
+        #[case] expected: bool,
+    ) {
+        let discord = Discord {
+            min_review_percent,
+            require_deck_playable,
+            ..Default::default()
+        };
+        let ratings = Ratings {
+            reviews: reviews.map(|(total_positive, total_negative)| ReviewSummary {
+                review_score_desc: String::new(),
+                total_positive,
+                total_negative,
+            }),
+            deck,
+        };
+
+        assert_eq!(expected, discord.passes_filters(&ratings));
+    }
 }
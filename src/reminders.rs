@@ -0,0 +1,123 @@
+//! This module provides reminders for sales that are about to end.
+
+use std::collections::HashMap;
+
+use futures::{StreamExt, TryStreamExt};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+use crate::{
+    Result,
+    alerts::{self, Alert},
+    framework, models, scheduler, util,
+};
+
+/// Reminds opted-in guilds about alerted sales ending within their reminder window.
+#[tracing::instrument(level = "error", skip(ctx, cancel))]
+pub async fn send_sale_end_reminders(
+    ctx: &framework::Data,
+    cancel: &CancellationToken,
+) -> Result<()> {
+    let now = chrono::Utc::now();
+
+    let mut guilds = ctx.repo.discord.get_sale_end_reminder_guilds().await?;
+    while let Some(discord) = guilds.next().await {
+        if cancel.is_cancelled() {
+            Err(scheduler::Cancelled)?
+        }
+        let discord = match discord {
+            Ok(x) => x,
+            Err(err) => {
+                error!(?err, "Failed to get guild");
+                continue;
+            }
+        };
+
+        if let Err(err) = remind_guild(ctx, &discord, now).await {
+            error!(
+                ?err,
+                guild_id = discord.server_id,
+                "Failed to send sale end reminders"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn remind_guild(
+    ctx: &framework::Data,
+    discord: &models::Discord,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<()> {
+    let Some(hours) = discord.sale_end_reminder_hours else {
+        return Ok(());
+    };
+    let deadline = now + chrono::Duration::hours(hours.into());
+
+    // Only remind about sales the guild was alerted of.
+    let junctions = ctx
+        .repo
+        .junction
+        .get_guild_junctions(discord.server_id)
+        .await?
+        .try_filter(|j| std::future::ready(j.is_trailing_sale_day))
+        .map_ok(|j| (j.app_id, j))
+        .try_collect::<HashMap<_, _>>()
+        .await?;
+    let app_ids = junctions.keys().copied().collect::<Vec<_>>();
+    let mut apps = ctx.repo.apps.get_apps(&app_ids).await?;
+
+    let mut reminders = Vec::new();
+    while let Some(app) = apps.try_next().await? {
+        let Some(discount_end) = app.price.as_ref().and_then(|p| p.discount_end) else {
+            continue;
+        };
+        let end = util::to_chrono(discount_end);
+        if end <= now || end > deadline {
+            continue;
+        }
+        let already_reminded = junctions
+            .get(&app.app_id)
+            .is_some_and(|j| j.reminded_discount_end == Some(discount_end));
+        if already_reminded {
+            continue;
+        }
+
+        reminders.push((app.app_id, discount_end, Alert::SaleEnding(app)));
+    }
+    if reminders.is_empty() {
+        return Ok(());
+    }
+
+    if ctx.dry_run {
+        info!(
+            guild_id = discord.server_id,
+            count = reminders.len(),
+            "Would send sale end reminders"
+        );
+        return Ok(());
+    }
+    info!(
+        guild_id = discord.server_id,
+        count = reminders.len(),
+        "Sending sale end reminders"
+    );
+    let alerts = reminders
+        .iter()
+        .map(|(_, _, alert)| alert.clone())
+        .collect::<Vec<_>>();
+    // Weekly guilds aren't sent reminders, so they're reminded if they switch back in time.
+    if !alerts::deliver_or_queue(ctx, discord, &alerts, now).await? {
+        return Ok(());
+    }
+
+    for (app_id, discount_end, _) in reminders {
+        ctx.repo
+            .junction
+            .set_reminded_discount_end(discord.server_id, app_id, discount_end)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 //! This module provides a repository for the apps collection.
 
 use futures::TryStreamExt;
-use mongodb::bson;
+use mongodb::{bson, options::ReturnDocument};
 
 use crate::{database, models};
 
@@ -16,7 +16,7 @@ impl AppsRepo {
     }
 
     /// Inserts the app if it's not present in the collection. Otherwise,
-    /// replaces it in the collection (as a way to update the app name).
+    /// replaces it in the collection (as a way to refresh the app's metadata).
     pub fn upsert_app(&self, app: &models::App) -> mongodb::action::Update<'_> {
         let query = bson::doc! { "app_id": app.app_id };
         let mut adoc = bson::to_document(app).expect("app should be serializable");
@@ -30,7 +30,8 @@ impl AppsRepo {
         self.coll.update_one(query, update).upsert(true)
     }
 
-    pub async fn remove_orphans(&self) -> mongodb::error::Result<()> {
+    /// Deletes apps no guild tracks, returning how many were deleted.
+    pub async fn remove_orphans(&self) -> mongodb::error::Result<u64> {
         let pipeline = [
             bson::doc! {
                 "$lookup": {
@@ -53,9 +54,32 @@ impl AppsRepo {
             .await?;
 
         let query = bson::doc! { "_id": { "$in": ids } };
-        self.coll.delete_many(query).await?;
+        let res = self.coll.delete_many(query).await?;
 
-        Ok(())
+        Ok(res.deleted_count)
+    }
+
+    /// Counts another check in a row that didn't find the app, returning the app
+    /// with the updated count. Upserting the app resets the count.
+    pub fn add_missing_check(
+        &self,
+        app_id: i32,
+    ) -> mongodb::action::FindOneAndUpdate<'_, models::App> {
+        let filter = bson::doc! { "app_id": app_id };
+        let update = bson::doc! { "$inc": { "missing_checks": 1 } };
+        self.coll
+            .find_one_and_update(filter, update)
+            .return_document(ReturnDocument::After)
+    }
+
+    pub fn get_app(&self, app_id: i32) -> mongodb::action::FindOne<'_, models::App> {
+        let filter = bson::doc! { "app_id": app_id };
+        self.coll.find_one(filter)
+    }
+
+    pub fn get_apps(&self, app_ids: &[i32]) -> mongodb::action::Find<'_, models::App> {
+        let filter = bson::doc! { "app_id": { "$in": app_ids } };
+        self.coll.find(filter)
     }
 
     pub async fn get_app_ids(&self) -> mongodb::error::Result<Vec<i32>> {
@@ -70,6 +94,7 @@ impl AppsRepo {
 
 #[cfg(test)]
 mod tests {
+    use futures::TryStreamExt;
     use pretty_assertions::assert_eq;
 
     use crate::{
@@ -115,6 +140,28 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn add_missing_check_increments_count_of_target_app_until_upserted() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = AppsRepo::new(&db);
+
+        let target = App { app_id: 0, ..Default::default() };
+        let other  = App { app_id: 1, ..Default::default() };
+        db.apps().insert_many([&target, &other]).await?;
+
+        repo.add_missing_check(0).await?;
+        let actual = repo.add_missing_check(0).await?;
+        assert_eq!(Some(App { missing_checks: 2, ..target.clone() }), actual);
+        assert_eq!(Some(other), repo.get_app(1).await?);
+
+        repo.upsert_app(&target).await?;
+        assert_eq!(Some(target), repo.get_app(0).await?);
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial_test::serial(database)]
     #[rustfmt::skip]
@@ -129,7 +176,8 @@ mod tests {
         let tracker_of_other = Junction { app_id: other.app_id, ..Default::default() };
         db.junction().insert_one(&tracker_of_other).await?;
 
-        repo.remove_orphans().await?;
+        let deleted = repo.remove_orphans().await?;
+        assert_eq!(1, deleted);
 
         let actual = db.apps().collect().await?;
         assert_eq!([other], actual[..]);
@@ -137,6 +185,40 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_app_gets_correct_app() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = AppsRepo::new(&db);
+
+        let expected = App { app_id: 0, ..Default::default() };
+        let other = App { app_id: 1, ..Default::default() };
+        db.apps().insert_many([&expected, &other]).await?;
+
+        let actual = repo.get_app(expected.app_id).await?;
+        assert_eq!(Some(expected), actual);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_apps_only_finds_targeted_apps() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = AppsRepo::new(&db);
+
+        let target = App { app_id: 0, ..Default::default() };
+        let other = App { app_id: 1, ..Default::default() };
+        db.apps().insert_many([&target, &other]).await?;
+
+        let actual = repo.get_apps(&[target.app_id]).await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([target], actual[..]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial_test::serial(database)]
     #[rustfmt::skip]
@@ -0,0 +1,118 @@
+//! This module provides a repository for the cursors collection.
+
+use mongodb::bson;
+
+use crate::{database, models};
+
+#[derive(Debug, Clone)]
+pub struct CursorsRepo {
+    coll: mongodb::Collection<models::CheckCursor>,
+}
+
+impl CursorsRepo {
+    pub fn new(db: &database::Database) -> Self {
+        Self { coll: db.cursors() }
+    }
+
+    /// Saves the cursor, replacing the previous cursor of the same name.
+    pub fn set_cursor(&self, cursor: &models::CheckCursor) -> mongodb::action::ReplaceOne<'_> {
+        let query = bson::doc! { "_id": &cursor.name };
+        self.coll.replace_one(query, cursor).upsert(true)
+    }
+
+    pub fn get_cursor(&self, name: &str) -> mongodb::action::FindOne<'_, models::CheckCursor> {
+        let filter = bson::doc! { "_id": name };
+        self.coll.find_one(filter)
+    }
+
+    pub fn remove_cursor(&self, name: &str) -> mongodb::action::Delete<'_> {
+        let query = bson::doc! { "_id": name };
+        self.coll.delete_one(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        Result,
+        database::{CollectionCollectAll, TestDatabase},
+        models::CheckCursor,
+        repos::cursors_repo::CursorsRepo,
+    };
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_cursor_replaces_previous_cursor() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = CursorsRepo::new(&db);
+
+        let previous = CheckCursor { name: "cursor".to_string(), last_app_id: 0, ..Default::default() };
+        let other    = CheckCursor { name: "other".to_string(),  last_app_id: 0, ..Default::default() };
+        db.cursors().insert_many([&previous, &other]).await?;
+
+        let expected = CheckCursor { last_app_id: 1, ..previous.clone() };
+        repo.set_cursor(&expected).await?;
+
+        let actual = db.cursors().collect().await?;
+        assert_eq!(2, actual.len(), "Collection doesn't have two records: {actual:?}");
+        assert!(actual.contains(&expected));
+        assert!(actual.contains(&other));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_cursor_inserts_first_cursor() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = CursorsRepo::new(&db);
+
+        let expected = CheckCursor { name: "cursor".to_string(), ..Default::default() };
+        repo.set_cursor(&expected).await?;
+
+        let actual = db.cursors().collect().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_cursor_finds_target_cursor() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = CursorsRepo::new(&db);
+
+        let target = CheckCursor { name: "cursor".to_string(), ..Default::default() };
+        let other  = CheckCursor { name: "other".to_string(),  ..Default::default() };
+        db.cursors().insert_many([&target, &other]).await?;
+
+        let actual = repo.get_cursor("cursor").await?;
+        assert_eq!(Some(target), actual);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn remove_cursor_only_deletes_target_cursor() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = CursorsRepo::new(&db);
+
+        let target = CheckCursor { name: "cursor".to_string(), ..Default::default() };
+        let other  = CheckCursor { name: "other".to_string(),  ..Default::default() };
+        db.cursors().insert_many([&target, &other]).await?;
+
+        repo.remove_cursor("cursor").await?;
+
+        let actual = db.cursors().collect().await?;
+        assert_eq!([other], actual[..]);
+
+        Ok(())
+    }
+}
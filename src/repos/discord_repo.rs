@@ -28,6 +28,153 @@ impl DiscordRepo {
         self.coll.update_one(query, update)
     }
 
+    pub fn set_delivery_mode(
+        &self,
+        guild_id: i64,
+        mode: models::DeliveryMode,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let mode = bson::to_bson(&mode).expect("delivery mode should be serializable");
+        let update = bson::doc! { "$set": { "delivery_mode": mode } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_timezone(
+        &self,
+        guild_id: i64,
+        timezone: chrono_tz::Tz,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "timezone": timezone.name() } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_last_digest_at(
+        &self,
+        guild_id: i64,
+        last_digest_at: bson::DateTime,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "last_digest_at": last_digest_at } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_quiet_hours(
+        &self,
+        guild_id: i64,
+        quiet_hours: Option<models::QuietHours>,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let quiet_hours = bson::to_bson(&quiet_hours).expect("quiet hours should be serializable");
+        let update = bson::doc! { "$set": { "quiet_hours": quiet_hours } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_sale_end_reminder_hours(
+        &self,
+        guild_id: i64,
+        hours: Option<i32>,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "sale_end_reminder_hours": hours } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_price_change_alerts(
+        &self,
+        guild_id: i64,
+        enabled: bool,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "price_change_alerts": enabled } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_auto_untrack_delisted(
+        &self,
+        guild_id: i64,
+        enabled: bool,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "auto_untrack_delisted": enabled } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_min_review_percent(
+        &self,
+        guild_id: i64,
+        min_review_percent: Option<i32>,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "min_review_percent": min_review_percent } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_require_deck_playable(
+        &self,
+        guild_id: i64,
+        required: bool,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "require_deck_playable": required } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_sale_event_announcements(
+        &self,
+        guild_id: i64,
+        enabled: bool,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "sale_event_announcements": enabled } };
+
+        self.coll.update_one(query, update)
+    }
+
+    pub fn set_announced_event_start(
+        &self,
+        guild_id: i64,
+        start: bson::DateTime,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! { "server_id": guild_id };
+        let update = bson::doc! { "$set": { "announced_event_start": start } };
+
+        self.coll.update_one(query, update)
+    }
+
+    /// Finds guilds that have a channel to send alerts to.
+    pub fn get_bound_guilds(&self) -> mongodb::action::Find<'_, models::Discord> {
+        let filter = bson::doc! { "channel_id": { "$ne": 0 } };
+        self.coll.find(filter)
+    }
+
+    /// Finds bound guilds that opted into sale event announcements.
+    pub fn get_sale_event_guilds(&self) -> mongodb::action::Find<'_, models::Discord> {
+        let filter = bson::doc! { "sale_event_announcements": true, "channel_id": { "$ne": 0 } };
+        self.coll.find(filter)
+    }
+
+    /// Finds guilds that opted into sale-ending reminders.
+    pub fn get_sale_end_reminder_guilds(&self) -> mongodb::action::Find<'_, models::Discord> {
+        let filter = bson::doc! { "sale_end_reminder_hours": { "$ne": null } };
+        self.coll.find(filter)
+    }
+
+    /// Finds bound guilds whose delivery mode is [`models::DeliveryMode::Weekly`].
+    pub fn get_weekly_digest_guilds(&self) -> mongodb::action::Find<'_, models::Discord> {
+        let filter = bson::doc! { "delivery_mode.kind": "weekly", "channel_id": { "$ne": 0 } };
+        self.coll.find(filter)
+    }
+
     pub fn get_guild(&self, guild_id: i64) -> mongodb::action::FindOne<'_, models::Discord> {
         let filter = bson::doc! { "server_id": guild_id };
         self.coll.find_one(filter)
@@ -51,6 +198,7 @@ impl DiscordRepo {
             server_id: guild_id,
             channel_id,
             sale_threshold: DEFAULT_SALE_THRESHOLD,
+            ..Default::default()
         };
         let ddoc = bson::to_document(&discord).expect("discord should be serializable");
         let update = bson::doc! { "$setOnInsert" : ddoc };
@@ -63,12 +211,14 @@ impl DiscordRepo {
 // #[serial_test::serial] must be defined fn-level UNDER #[rstest] or
 // strange things happen with futures. See Also: rstest Issue #302
 mod tests {
+    use futures::TryStreamExt;
+    use mongodb::bson;
     use pretty_assertions::assert_eq;
 
     use crate::{
         Result,
         database::{CollectionCollectAll, TestDatabase},
-        models::Discord,
+        models::{DeliveryMode, Discord, QuietHours},
         repos::discord_repo::DiscordRepo,
     };
 
@@ -120,6 +270,359 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_delivery_mode_only_updates_delivery_mode_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        let new_mode = DeliveryMode::Auto { threshold: 5 };
+        repo.set_delivery_mode(target.server_id, new_mode).await?;
+
+        // Update target's expected delivery_mode
+        target.delivery_mode = new_mode;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_timezone_only_updates_timezone_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        let new_timezone = chrono_tz::Asia::Tokyo;
+        repo.set_timezone(target.server_id, new_timezone).await?;
+
+        // Update target's expected timezone
+        target.timezone = new_timezone;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_last_digest_at_only_updates_last_digest_at_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        let new_last_digest_at = bson::DateTime::from_millis(1_000);
+        repo.set_last_digest_at(target.server_id, new_last_digest_at).await?;
+
+        // Update target's expected last_digest_at
+        target.last_digest_at = Some(new_last_digest_at);
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_quiet_hours_only_updates_quiet_hours_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        let new_quiet_hours = Some(QuietHours { start: 22, end: 7 });
+        repo.set_quiet_hours(target.server_id, new_quiet_hours).await?;
+
+        // Update target's expected quiet_hours
+        target.quiet_hours = new_quiet_hours;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_sale_end_reminder_hours_only_updates_reminder_hours_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        let new_hours = Some(12);
+        repo.set_sale_end_reminder_hours(target.server_id, new_hours).await?;
+
+        // Update target's expected sale_end_reminder_hours
+        target.sale_end_reminder_hours = new_hours;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_price_change_alerts_only_updates_price_change_alerts_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        repo.set_price_change_alerts(target.server_id, true).await?;
+
+        // Update target's expected price_change_alerts
+        target.price_change_alerts = true;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_auto_untrack_delisted_only_updates_auto_untrack_delisted_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        repo.set_auto_untrack_delisted(target.server_id, true).await?;
+
+        // Update target's expected auto_untrack_delisted
+        target.auto_untrack_delisted = true;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_min_review_percent_only_updates_min_review_percent_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        let new_min_review_percent = Some(80);
+        repo.set_min_review_percent(target.server_id, new_min_review_percent).await?;
+
+        // Update target's expected min_review_percent
+        target.min_review_percent = new_min_review_percent;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_require_deck_playable_only_updates_require_deck_playable_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        repo.set_require_deck_playable(target.server_id, true).await?;
+
+        // Update target's expected require_deck_playable
+        target.require_deck_playable = true;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_sale_event_announcements_only_updates_sale_event_announcements_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        repo.set_sale_event_announcements(target.server_id, true).await?;
+
+        // Update target's expected sale_event_announcements
+        target.sale_event_announcements = true;
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_announced_event_start_only_updates_announced_event_start_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mut target = Discord { server_id: 0, ..Default::default() };
+        let other      = Discord { server_id: 1, ..Default::default() };
+        db.discord().insert_many([&target, &other]).await?;
+
+        let new_start = bson::DateTime::from_millis(1_000);
+        repo.set_announced_event_start(target.server_id, new_start).await?;
+
+        // Update target's expected announced_event_start
+        target.announced_event_start = Some(new_start);
+
+        let actual = db.discord().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_bound_guilds_only_finds_guilds_with_channel() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let expected = Discord { server_id: 0, channel_id: 1, ..Default::default() };
+        let other    = Discord { server_id: 1, channel_id: 0, ..Default::default() };
+        db.discord().insert_many([&expected, &other]).await?;
+
+        let actual = repo.get_bound_guilds().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_sale_event_guilds_only_finds_opted_in_guilds() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let expected = Discord { server_id: 0, channel_id: 1, sale_event_announcements: true, ..Default::default() };
+        let other    = Discord { server_id: 1, channel_id: 1, sale_event_announcements: false, ..Default::default() };
+        db.discord().insert_many([&expected, &other]).await?;
+
+        let actual = repo.get_sale_event_guilds().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_sale_event_guilds_skips_unbound_guilds() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let expected = Discord { server_id: 0, channel_id: 1, sale_event_announcements: true, ..Default::default() };
+        let unbound  = Discord { server_id: 1, channel_id: 0, sale_event_announcements: true, ..Default::default() };
+        db.discord().insert_many([&expected, &unbound]).await?;
+
+        let actual = repo.get_sale_event_guilds().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_sale_end_reminder_guilds_only_finds_opted_in_guilds() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let expected = Discord { server_id: 0, sale_end_reminder_hours: Some(12), ..Default::default() };
+        let other    = Discord { server_id: 1, sale_end_reminder_hours: None, ..Default::default() };
+        db.discord().insert_many([&expected, &other]).await?;
+
+        let actual = repo.get_sale_end_reminder_guilds().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_weekly_digest_guilds_only_finds_weekly_guilds() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mode = DeliveryMode::Weekly { weekday: chrono::Weekday::Mon, hour: 17 };
+        let expected = Discord { server_id: 0, channel_id: 1, delivery_mode: mode, ..Default::default() };
+        let other    = Discord { server_id: 1, channel_id: 1, delivery_mode: DeliveryMode::Digest, ..Default::default() };
+        db.discord().insert_many([&expected, &other]).await?;
+
+        let actual = repo.get_weekly_digest_guilds().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_weekly_digest_guilds_skips_unbound_guilds() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = DiscordRepo::new(&db);
+
+        let mode = DeliveryMode::Weekly { weekday: chrono::Weekday::Mon, hour: 17 };
+        let expected = Discord { server_id: 0, channel_id: 1, delivery_mode: mode, ..Default::default() };
+        let unbound  = Discord { server_id: 1, channel_id: 0, delivery_mode: mode, ..Default::default() };
+        db.discord().insert_many([&expected, &unbound]).await?;
+
+        let actual = repo.get_weekly_digest_guilds().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial_test::serial(database)]
     #[rustfmt::skip]
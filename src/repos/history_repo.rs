@@ -0,0 +1,207 @@
+//! This module provides a repository for the history collection.
+
+use std::time::Duration;
+
+use futures::TryStreamExt;
+use mongodb::{IndexModel, bson, options::IndexOptions};
+
+use crate::{database, models};
+
+/// Observations older than this are deleted, so lowest prices in digests are
+/// the lowest within this window.
+const RETENTION: Duration = Duration::from_secs(2 * 365 * 24 * 60 * 60);
+
+#[derive(Debug, Clone)]
+pub struct HistoryRepo {
+    coll: mongodb::Collection<models::Observation>,
+}
+
+impl HistoryRepo {
+    pub fn new(db: &database::Database) -> Self {
+        Self { coll: db.history() }
+    }
+
+    /// Creates the indexes for looking up an app's observations by time and for
+    /// deleting observations past [`RETENTION`]. Existing indexes are kept.
+    pub fn create_indexes(&self) -> mongodb::action::CreateIndex<'_, mongodb::action::Multiple> {
+        let by_app = IndexModel::builder()
+            .keys(bson::doc! { "app_id": 1, "observed_at": -1 })
+            .build();
+        let retention = IndexModel::builder()
+            .keys(bson::doc! { "observed_at": 1 })
+            .options(IndexOptions::builder().expire_after(RETENTION).build())
+            .build();
+        self.coll.create_indexes([by_app, retention])
+    }
+
+    pub fn add_observation(
+        &self,
+        observation: &models::Observation,
+    ) -> mongodb::action::InsertOne<'_> {
+        self.coll.insert_one(observation)
+    }
+
+    /// Finds the most recent observation of the app.
+    pub fn get_latest_observation(
+        &self,
+        app_id: i32,
+    ) -> mongodb::action::FindOne<'_, models::Observation> {
+        let filter = bson::doc! { "app_id": app_id };
+        self.coll
+            .find_one(filter)
+            .sort(bson::doc! { "observed_at": -1 })
+    }
+
+    /// Finds observations of the apps made at or after `since`, oldest first.
+    pub fn get_observations_since(
+        &self,
+        app_ids: &[i32],
+        since: bson::DateTime,
+    ) -> mongodb::action::Find<'_, models::Observation> {
+        let filter = bson::doc! {
+            "app_id": { "$in": app_ids },
+            "observed_at": { "$gte": since },
+        };
+        self.coll.find(filter).sort(bson::doc! { "observed_at": 1 })
+    }
+
+    /// Summarizes observations of the apps made before `before`. Apps without
+    /// any such observations are omitted.
+    pub async fn get_summaries_before(
+        &self,
+        app_ids: &[i32],
+        before: bson::DateTime,
+    ) -> mongodb::error::Result<Vec<models::ObservationSummary>> {
+        let pipeline = [
+            bson::doc! {
+                "$match": {
+                    "app_id": { "$in": app_ids },
+                    "observed_at": { "$lt": before },
+                }
+            },
+            bson::doc! { "$sort": { "observed_at": 1 } },
+            bson::doc! {
+                "$group": {
+                    "_id": "$app_id",
+                    "lowest_price": { "$min": "$price.final_price" },
+                    "coming_soon": { "$last": "$coming_soon" },
+                }
+            },
+        ];
+
+        self.coll
+            .aggregate(pipeline)
+            .with_type::<models::ObservationSummary>()
+            .await?
+            .try_collect()
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use mongodb::bson;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        Result,
+        database::{CollectionCollectAll, TestDatabase},
+        models::{Observation, ObservationSummary, Price},
+        repos::history_repo::HistoryRepo,
+    };
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    async fn create_indexes_can_run_repeatedly() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = HistoryRepo::new(&db);
+
+        repo.create_indexes().await?;
+        repo.create_indexes().await?;
+
+        let mut actual = db.history().list_index_names().await?;
+        actual.sort();
+        assert_eq!(
+            ["_id_", "app_id_1_observed_at_-1", "observed_at_1"],
+            actual[..]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    async fn add_observation_inserts_observation_into_collection() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = HistoryRepo::new(&db);
+
+        let expected = Observation::default();
+        repo.add_observation(&expected).await?;
+
+        let actual = db.history().collect().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_latest_observation_finds_most_recent_observation_of_target_app() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = HistoryRepo::new(&db);
+
+        let old      = Observation { app_id: 0, observed_at: bson::DateTime::from_millis(0), ..Default::default() };
+        let expected = Observation { app_id: 0, observed_at: bson::DateTime::from_millis(1_000), ..Default::default() };
+        let other    = Observation { app_id: 1, observed_at: bson::DateTime::from_millis(2_000), ..Default::default() };
+        db.history().insert_many([&old, &expected, &other]).await?;
+
+        let actual = repo.get_latest_observation(0).await?;
+        assert_eq!(Some(expected), actual);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_observations_since_only_finds_recent_observations_of_targeted_apps() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = HistoryRepo::new(&db);
+
+        let since = bson::DateTime::from_millis(1_000);
+        let old      = Observation { app_id: 0, observed_at: bson::DateTime::from_millis(0), ..Default::default() };
+        let expected = Observation { app_id: 0, observed_at: since, ..Default::default() };
+        let other    = Observation { app_id: 1, observed_at: since, ..Default::default() };
+        db.history().insert_many([&old, &expected, &other]).await?;
+
+        let actual = repo.get_observations_since(&[0], since).await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_summaries_before_aggregates_older_observations() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = HistoryRepo::new(&db);
+
+        let price = |final_price| Some(Price { final_price, ..Default::default() });
+        let before = bson::DateTime::from_millis(2_000);
+        db.history().insert_many([
+            Observation { app_id: 0, observed_at: bson::DateTime::from_millis(0), coming_soon: true, price: None, ..Default::default() },
+            Observation { app_id: 0, observed_at: bson::DateTime::from_millis(1_000), coming_soon: false, price: price(500), ..Default::default() },
+            // Too recent
+            Observation { app_id: 0, observed_at: before, coming_soon: false, price: price(100), ..Default::default() },
+        ]).await?;
+
+        let actual = repo.get_summaries_before(&[0], before).await?;
+        let expected = ObservationSummary { app_id: 0, lowest_price: Some(500), coming_soon: false };
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+}
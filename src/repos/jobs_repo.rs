@@ -0,0 +1,166 @@
+//! This module provides a repository for the jobs collection.
+
+use mongodb::bson;
+
+use crate::{database, models};
+
+#[derive(Debug, Clone)]
+pub struct JobsRepo {
+    coll: mongodb::Collection<models::JobRun>,
+}
+
+impl JobsRepo {
+    pub fn new(db: &database::Database) -> Self {
+        Self { coll: db.jobs() }
+    }
+
+    /// Records the start of a run, replacing the job's previous run.
+    pub fn start_run(&self, run: &models::JobRun) -> mongodb::action::ReplaceOne<'_> {
+        let query = bson::doc! { "_id": &run.job };
+        self.coll.replace_one(query, run).upsert(true)
+    }
+
+    /// Records how `run` finished, unless a later run has since replaced it.
+    pub fn finish_run(
+        &self,
+        run: &models::JobRun,
+        status: models::JobStatus,
+        finished_at: bson::DateTime,
+        error: Option<&str>,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! {
+            "_id": &run.job,
+            "holder": &run.holder,
+            "started_at": run.started_at,
+        };
+        let status = bson::to_bson(&status).expect("job status should be serializable");
+        let update = bson::doc! {
+            "$set": {
+                "status": status,
+                "finished_at": finished_at,
+                "error": error,
+            }
+        };
+        self.coll.update_one(query, update)
+    }
+
+    /// Finds the last run of each job that has run.
+    pub fn get_runs(&self) -> mongodb::action::Find<'_, models::JobRun> {
+        self.coll.find(bson::doc! {}).sort(bson::doc! { "_id": 1 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use mongodb::bson;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        Result,
+        database::{CollectionCollectAll, TestDatabase},
+        models::{JobRun, JobStatus, JobTrigger},
+        repos::jobs_repo::JobsRepo,
+    };
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn start_run_replaces_previous_run() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JobsRepo::new(&db);
+
+        let previous = JobRun { job: "job".to_string(),   status: JobStatus::Failed, error: Some("error".to_string()), ..Default::default() };
+        let other    = JobRun { job: "other".to_string(), ..Default::default() };
+        db.jobs().insert_many([&previous, &other]).await?;
+
+        let expected = JobRun { job: "job".to_string(), trigger: JobTrigger::Manual, ..Default::default() };
+        repo.start_run(&expected).await?;
+
+        let actual = db.jobs().collect().await?;
+        assert_eq!(2, actual.len(), "Collection doesn't have two records: {actual:?}");
+        assert!(actual.contains(&expected));
+        assert!(actual.contains(&other));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn start_run_inserts_first_run() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JobsRepo::new(&db);
+
+        let expected = JobRun { job: "job".to_string(), ..Default::default() };
+        repo.start_run(&expected).await?;
+
+        let actual = db.jobs().collect().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn finish_run_sets_outcome_of_target_job() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JobsRepo::new(&db);
+
+        let target = JobRun { job: "job".to_string(),   ..Default::default() };
+        let other  = JobRun { job: "other".to_string(), ..Default::default() };
+        db.jobs().insert_many([&target, &other]).await?;
+
+        let finished_at = bson::DateTime::from_millis(1_000);
+        repo.finish_run(&target, JobStatus::Failed, finished_at, Some("error")).await?;
+
+        let expected = JobRun {
+            status: JobStatus::Failed,
+            finished_at: Some(finished_at),
+            error: Some("error".to_string()),
+            ..target
+        };
+        let actual = db.jobs().collect().await?;
+        assert!(actual.contains(&expected), "{actual:?}");
+        assert!(actual.contains(&other), "{actual:?}");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn finish_run_doesnt_change_later_run() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JobsRepo::new(&db);
+
+        let earlier = JobRun { job: "job".to_string(), holder: "holder".to_string(), started_at: bson::DateTime::from_millis(0),     ..Default::default() };
+        let later   = JobRun { job: "job".to_string(), holder: "other".to_string(),  started_at: bson::DateTime::from_millis(1_000), ..Default::default() };
+        db.jobs().insert_one(&later).await?;
+
+        repo.finish_run(&earlier, JobStatus::Failed, bson::DateTime::from_millis(2_000), Some("error")).await?;
+
+        let actual = db.jobs().collect().await?;
+        assert_eq!([later], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_runs_finds_runs_by_job_name() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JobsRepo::new(&db);
+
+        let first  = JobRun { job: "a".to_string(), ..Default::default() };
+        let second = JobRun { job: "b".to_string(), ..Default::default() };
+        db.jobs().insert_many([&second, &first]).await?;
+
+        let actual = repo.get_runs().await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([first, second], actual[..]);
+
+        Ok(())
+    }
+}
@@ -95,6 +95,34 @@ impl JunctionRepo {
             .await)
     }
 
+    /// Gets the `limit` apps tracked by the most guilds, most tracked first.
+    pub async fn get_most_tracked_apps(
+        &self,
+        limit: i64,
+    ) -> mongodb::error::Result<Vec<models::TrackedApp>> {
+        let pipeline = [
+            bson::doc! { "$group": { "_id": "$app_id", "trackers": { "$sum": 1 } } },
+            bson::doc! { "$sort": { "trackers": -1, "_id": 1 } },
+            bson::doc! { "$limit": limit },
+            bson::doc! {
+                "$lookup": {
+                    "from": database::APPS_COLL,
+                    "localField": "_id",
+                    "foreignField": "app_id",
+                    "as": "apps",
+                }
+            },
+        ];
+
+        self.coll
+            .aggregate(pipeline)
+            .with_type::<TrackedAppAggregate>()
+            .await?
+            .map_ok(Into::into)
+            .try_collect()
+            .await
+    }
+
     pub fn clear_junctions(&self, guild_id: i64) -> mongodb::action::Delete<'_> {
         let query = bson::doc! { "server_id": guild_id };
         self.coll.delete_many(query)
@@ -127,6 +155,26 @@ impl JunctionRepo {
         self.coll.find(filter)
     }
 
+    pub fn get_junction(
+        &self,
+        guild_id: i64,
+        app_id: i32,
+    ) -> mongodb::action::FindOne<'_, models::Junction> {
+        let filter = bson::doc! {
+            "server_id": guild_id,
+            "app_id": app_id,
+        };
+        self.coll.find_one(filter)
+    }
+
+    pub fn get_guild_junctions(
+        &self,
+        guild_id: i64,
+    ) -> mongodb::action::Find<'_, models::Junction> {
+        let filter = bson::doc! { "server_id": guild_id };
+        self.coll.find(filter)
+    }
+
     pub fn update_junction(&self, junction: &models::Junction) -> mongodb::action::Update<'_> {
         let query = bson::doc! { "_id": junction.id };
         let jdoc = bson::to_document(junction).expect("junction should be serializable");
@@ -134,6 +182,39 @@ impl JunctionRepo {
         self.coll.update_one(query, update)
     }
 
+    /// Sets the target price of the guild's junctions with the apps, or removes
+    /// it if `target_price` is `None`.
+    pub fn set_target_prices(
+        &self,
+        guild_id: i64,
+        target_price: Option<i64>,
+        app_ids: &[i32],
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! {
+            "server_id": guild_id,
+            "app_id": { "$in": app_ids },
+        };
+        let update = match target_price {
+            Some(price) => bson::doc! { "$set": { "target_price": price } },
+            None => bson::doc! { "$unset": { "target_price": "" } },
+        };
+        self.coll.update_many(query, update)
+    }
+
+    pub fn set_reminded_discount_end(
+        &self,
+        guild_id: i64,
+        app_id: i32,
+        discount_end: bson::DateTime,
+    ) -> mongodb::action::Update<'_> {
+        let query = bson::doc! {
+            "server_id": guild_id,
+            "app_id": app_id,
+        };
+        let update = bson::doc! { "$set": { "reminded_discount_end": discount_end } };
+        self.coll.update_one(query, update)
+    }
+
     pub fn remove_junction(&self, guild_id: i64, app_id: i32) -> mongodb::action::Delete<'_> {
         let query = bson::doc! {
             "server_id": guild_id,
@@ -159,26 +240,59 @@ impl TryInto<models::AppListing> for AppListingAggregate {
             return Err(self.junction.id);
         }
         let models::App {
-            app_id, app_name, ..
+            app_id,
+            app_name,
+            price,
+            release_date,
+            ..
         } = self.apps.swap_remove(0);
 
         Ok(models::AppListing {
             app_id,
             app_name,
             sale_threshold: self.junction.sale_threshold,
+            discount_percent: price.map(|p| p.discount_percent),
+            added_at: self.junction.id.timestamp(),
+            upcoming_release_date: (self.junction.coming_soon && !release_date.is_empty())
+                .then_some(release_date),
         })
     }
 }
 
+#[derive(Clone, serde::Deserialize)]
+struct TrackedAppAggregate {
+    #[serde(rename = "_id")]
+    app_id: i32,
+    trackers: i32,
+    apps: Vec<models::App>,
+}
+
+impl From<TrackedAppAggregate> for models::TrackedApp {
+    fn from(mut value: TrackedAppAggregate) -> Self {
+        // The app may have been removed as an orphan before its junctions.
+        let app_name = if value.apps.is_empty() {
+            "Unknown".to_string()
+        } else {
+            value.apps.swap_remove(0).app_name
+        };
+        models::TrackedApp {
+            app_id: value.app_id,
+            app_name,
+            trackers: value.trackers,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::TryStreamExt;
+    use mongodb::bson;
     use pretty_assertions::assert_eq;
 
     use crate::{
         Result,
         database::{CollectionCollectAll, TestDatabase},
-        models::{App, AppListing, Discord, Junction},
+        models::{App, AppListing, Discord, Junction, Price, TrackedApp},
         repos::junction_repo::JunctionRepo,
     };
 
@@ -222,15 +336,27 @@ mod tests {
         let server_id = 0;
         let server_threshold = 1;
         let junction_threshold = 2;
-        let expected = AppListing {
+        let junction = Junction {
+            server_id,
             app_id: 1,
+            sale_threshold: Some(junction_threshold),
+            coming_soon: true,
+            ..Default::default()
+        };
+        let expected = AppListing {
+            app_id: junction.app_id,
             app_name: "name".to_string(),
             sale_threshold: Some(junction_threshold),
+            discount_percent: Some(50),
+            added_at: junction.id.timestamp(),
+            upcoming_release_date: Some("Aug 14, 2026".to_string()),
         };
         db.apps().insert_one(
             App {
                 app_id: expected.app_id,
                 app_name: expected.app_name.clone(),
+                price: Some(Price { discount_percent: 50, ..Default::default() }),
+                release_date: "Aug 14, 2026".to_string(),
                 ..Default::default()
             }
         ).await?;
@@ -241,14 +367,7 @@ mod tests {
                 ..Default::default()
             }
         ).await?;
-        db.junction().insert_one(
-            Junction {
-                server_id,
-                app_id: expected.app_id,
-                sale_threshold: Some(junction_threshold),
-                ..Default::default()
-            }
-        ).await?;
+        db.junction().insert_one(&junction).await?;
 
         let actual = repo.get_app_listings(server_id).await?;
         assert_eq!(1, actual.len(), "{actual:?}");
@@ -257,6 +376,34 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_most_tracked_apps_counts_trackers_in_order() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JunctionRepo::new(&db);
+
+        db.apps().insert_many([
+            App { app_id: 0, app_name: "zero".to_string(), ..Default::default() },
+            App { app_id: 1, app_name: "one".to_string(), ..Default::default() },
+        ]).await?;
+        db.junction().insert_many([
+            Junction { server_id: 0, app_id: 0, ..Default::default() },
+            Junction { server_id: 0, app_id: 1, ..Default::default() },
+            Junction { server_id: 1, app_id: 1, ..Default::default() },
+            Junction { server_id: 1, app_id: 2, ..Default::default() },
+        ]).await?;
+
+        let actual = repo.get_most_tracked_apps(2).await?;
+        let expected = [
+            TrackedApp { app_id: 1, app_name: "one".to_string(), trackers: 2 },
+            TrackedApp { app_id: 0, app_name: "zero".to_string(), trackers: 1 },
+        ];
+        assert_eq!(expected, actual[..]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial_test::serial(database)]
     #[rustfmt::skip]
@@ -398,6 +545,41 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_junction_gets_correct_junction() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JunctionRepo::new(&db);
+
+        let expected = Junction { server_id: 0, app_id: 0, ..Default::default() };
+        let other_app = Junction { server_id: 0, app_id: 1, ..Default::default() };
+        let other_guild = Junction { server_id: 1, app_id: 0, ..Default::default() };
+        db.junction().insert_many([&expected, &other_app, &other_guild]).await?;
+
+        let actual = repo.get_junction(expected.server_id, expected.app_id).await?;
+        assert_eq!(Some(expected), actual);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_guild_junctions_only_finds_junctions_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JunctionRepo::new(&db);
+
+        let expected = Junction { server_id: 0, ..Default::default() };
+        let other = Junction { server_id: 1, ..Default::default() };
+        db.junction().insert_many([&expected, &other]).await?;
+
+        let actual = repo.get_guild_junctions(expected.server_id).await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([expected], actual[..]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial_test::serial(database)]
     #[rustfmt::skip]
@@ -419,6 +601,55 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_target_prices_only_updates_target_apps_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JunctionRepo::new(&db);
+
+        let mut target = Junction { server_id: 0, app_id: 0, ..Default::default() };
+        let mut cleared = Junction { server_id: 0, app_id: 1, target_price: Some(999), ..Default::default() };
+        let other_app = Junction { server_id: 0, app_id: 2, ..Default::default() };
+        let other_guild = Junction { server_id: 1, app_id: 0, ..Default::default() };
+        db.junction().insert_many([&target, &cleared, &other_app, &other_guild]).await?;
+
+        repo.set_target_prices(0, Some(1999), &[target.app_id]).await?;
+        repo.set_target_prices(0, None, &[cleared.app_id]).await?;
+
+        // Update expected target_prices
+        target.target_price = Some(1999);
+        cleared.target_price = None;
+
+        let actual = db.junction().collect().await?;
+        assert_eq!([target, cleared, other_app, other_guild], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn set_reminded_discount_end_only_updates_target() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = JunctionRepo::new(&db);
+
+        let mut target = Junction { server_id: 0, app_id: 0, ..Default::default() };
+        let other = Junction { server_id: 0, app_id: 1, ..Default::default() };
+        db.junction().insert_many([&target, &other]).await?;
+
+        let discount_end = bson::DateTime::from_millis(1_000);
+        repo.set_reminded_discount_end(target.server_id, target.app_id, discount_end).await?;
+
+        // Update target's expected reminded_discount_end
+        target.reminded_discount_end = Some(discount_end);
+
+        let actual = db.junction().collect().await?;
+        assert_eq!([target, other], actual[..]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial_test::serial(database)]
     #[rustfmt::skip]
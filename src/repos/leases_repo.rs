@@ -0,0 +1,203 @@
+//! This module provides a repository for the leases collection.
+
+use std::time::Duration;
+
+use mongodb::{
+    bson,
+    error::{ErrorKind, WriteFailure},
+};
+
+use crate::{database, models};
+
+/// MongoDB's error code for a unique index violation.
+const DUPLICATE_KEY_CODE: i32 = 11000;
+
+#[derive(Debug, Clone)]
+pub struct LeasesRepo {
+    coll: mongodb::Collection<models::Lease>,
+}
+
+impl LeasesRepo {
+    pub fn new(db: &database::Database) -> Self {
+        Self { coll: db.leases() }
+    }
+
+    /// Acquires the lease for `holder` if it's free or expired, or renews it if
+    /// `holder` already holds it. Either way, it then expires after `ttl`.
+    /// Returns whether `holder` holds the lease.
+    ///
+    /// Expiry is judged by the database's clock so holders' clocks needn't agree.
+    pub async fn try_acquire(
+        &self,
+        name: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> mongodb::error::Result<bool> {
+        let filter = bson::doc! {
+            "_id": name,
+            "$or": [
+                { "holder": holder },
+                { "$expr": { "$lte": ["$expires_at", "$$NOW"] } },
+            ],
+        };
+        let update = vec![bson::doc! {
+            "$set": {
+                "holder": holder,
+                "expires_at": { "$add": ["$$NOW", ttl.as_millis() as i64] },
+            }
+        }];
+        match self.coll.update_one(filter, update).upsert(true).await {
+            Ok(_) => Ok(true),
+            // The lease is held by another holder, so inserting it again collides.
+            Err(err) if is_duplicate_key(&err) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Releases the lease if `holder` holds it so another can take over early.
+    pub fn release(&self, name: &str, holder: &str) -> mongodb::action::Delete<'_> {
+        let query = bson::doc! { "_id": name, "holder": holder };
+        self.coll.delete_one(query)
+    }
+
+    pub fn get_lease(&self, name: &str) -> mongodb::action::FindOne<'_, models::Lease> {
+        let filter = bson::doc! { "_id": name };
+        self.coll.find_one(filter)
+    }
+}
+
+fn is_duplicate_key(err: &mongodb::error::Error) -> bool {
+    matches!(
+        &*err.kind,
+        ErrorKind::Write(WriteFailure::WriteError(err)) if err.code == DUPLICATE_KEY_CODE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use mongodb::bson;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        Result,
+        database::{CollectionCollectAll, TestDatabase},
+        models::Lease,
+        repos::leases_repo::LeasesRepo,
+    };
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn try_acquire_inserts_missing_lease() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = LeasesRepo::new(&db);
+
+        let acquired = repo.try_acquire("lease", "holder", TTL).await?;
+        assert!(acquired);
+
+        let actual = db.leases().collect().await?;
+        assert_eq!(1, actual.len(), "Collection doesn't have one record: {actual:?}");
+        assert_eq!("holder", actual[0].holder);
+        assert!(actual[0].expires_at > bson::DateTime::now());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn try_acquire_renews_own_lease() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = LeasesRepo::new(&db);
+
+        let existing = Lease { name: "lease".to_string(), holder: "holder".to_string(), ..Default::default() };
+        db.leases().insert_one(&existing).await?;
+
+        let acquired = repo.try_acquire("lease", "holder", TTL).await?;
+        assert!(acquired);
+
+        let actual = db.leases().collect().await?;
+        assert_eq!("holder", actual[0].holder);
+        assert!(actual[0].expires_at > existing.expires_at);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn try_acquire_takes_over_expired_lease() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = LeasesRepo::new(&db);
+
+        let expired = Lease { name: "lease".to_string(), holder: "other".to_string(), expires_at: bson::DateTime::from_millis(0) };
+        db.leases().insert_one(expired).await?;
+
+        let acquired = repo.try_acquire("lease", "holder", TTL).await?;
+        assert!(acquired);
+
+        let actual = db.leases().collect().await?;
+        assert_eq!("holder", actual[0].holder);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn try_acquire_fails_while_other_holds_lease() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = LeasesRepo::new(&db);
+
+        let existing = Lease { name: "lease".to_string(), holder: "other".to_string(), expires_at: bson::DateTime::MAX };
+        db.leases().insert_one(&existing).await?;
+
+        let acquired = repo.try_acquire("lease", "holder", TTL).await?;
+        assert!(!acquired);
+
+        let actual = db.leases().collect().await?;
+        assert_eq!([existing], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn release_only_deletes_own_lease() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = LeasesRepo::new(&db);
+
+        let other = Lease { name: "lease".to_string(), holder: "other".to_string(), expires_at: bson::DateTime::MAX };
+        db.leases().insert_one(&other).await?;
+
+        repo.release("lease", "holder").await?;
+        assert_eq!([other], db.leases().collect().await?[..]);
+
+        repo.release("lease", "other").await?;
+        assert_eq!(0, db.leases().collect().await?.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_lease_finds_target_lease() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = LeasesRepo::new(&db);
+
+        let target = Lease { name: "lease".to_string(), holder: "holder".to_string(), expires_at: bson::DateTime::MAX };
+        let other  = Lease { name: "other".to_string(),  holder: "holder".to_string(), expires_at: bson::DateTime::MAX };
+        db.leases().insert_many([&target, &other]).await?;
+
+        let actual = repo.get_lease("lease").await?;
+        assert_eq!(Some(target), actual);
+
+        Ok(())
+    }
+}
@@ -5,32 +5,60 @@ use std::sync::Arc;
 use crate::database;
 
 mod apps_repo;
+mod cursors_repo;
 mod discord_repo;
+mod history_repo;
+mod jobs_repo;
 mod junction_repo;
+mod leases_repo;
+mod queue_repo;
 
 #[derive(Clone)]
 pub struct Repo {
     db: Arc<database::Database>,
     pub apps: apps_repo::AppsRepo,
+    pub cursors: cursors_repo::CursorsRepo,
     pub discord: discord_repo::DiscordRepo,
+    pub history: history_repo::HistoryRepo,
+    pub jobs: jobs_repo::JobsRepo,
     pub junction: junction_repo::JunctionRepo,
+    pub leases: leases_repo::LeasesRepo,
+    pub queue: queue_repo::QueueRepo,
 }
 
 impl Repo {
     pub fn new(db: Arc<database::Database>) -> Self {
         let apps = apps_repo::AppsRepo::new(&db);
+        let cursors = cursors_repo::CursorsRepo::new(&db);
         let discord = discord_repo::DiscordRepo::new(&db);
+        let history = history_repo::HistoryRepo::new(&db);
+        let jobs = jobs_repo::JobsRepo::new(&db);
         let junction = junction_repo::JunctionRepo::new(&db);
+        let leases = leases_repo::LeasesRepo::new(&db);
+        let queue = queue_repo::QueueRepo::new(&db);
 
         Self {
             db,
             apps,
+            cursors,
             discord,
+            history,
+            jobs,
             junction,
+            leases,
+            queue,
         }
     }
 
     pub async fn start_session(&self) -> mongodb::error::Result<mongodb::ClientSession> {
         self.db.start_session().await
     }
+
+    pub async fn ping(&self) -> mongodb::error::Result<()> {
+        self.db.ping().await
+    }
+
+    pub async fn shutdown(&self) {
+        self.db.shutdown().await
+    }
 }
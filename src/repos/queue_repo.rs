@@ -0,0 +1,143 @@
+//! This module provides a repository for the queue collection.
+
+use mongodb::bson;
+
+use crate::{alerts, database, models};
+
+#[derive(Debug, Clone)]
+pub struct QueueRepo {
+    coll: mongodb::Collection<models::QueuedAlert>,
+}
+
+impl QueueRepo {
+    pub fn new(db: &database::Database) -> Self {
+        Self { coll: db.queue() }
+    }
+
+    pub fn add_alerts(
+        &self,
+        guild_id: i64,
+        alerts: &[alerts::Alert],
+    ) -> mongodb::action::InsertMany<'_> {
+        let queued = alerts.iter().map(|alert| models::QueuedAlert {
+            id: Default::default(),
+            server_id: guild_id,
+            alert: alert.clone(),
+        });
+        self.coll.insert_many(queued)
+    }
+
+    /// Gets the ids of guilds with queued alerts.
+    pub async fn get_queued_guild_ids(&self) -> mongodb::error::Result<Vec<i64>> {
+        let ids = self
+            .coll
+            .distinct("server_id", bson::doc! {})
+            .await?
+            .into_iter()
+            .filter_map(|id| id.as_i64())
+            .collect();
+        Ok(ids)
+    }
+
+    /// Finds the guild's queued alerts, oldest first.
+    pub fn get_alerts(&self, guild_id: i64) -> mongodb::action::Find<'_, models::QueuedAlert> {
+        let filter = bson::doc! { "server_id": guild_id };
+        self.coll.find(filter).sort(bson::doc! { "_id": 1 })
+    }
+
+    pub fn remove_alerts(&self, ids: &[bson::oid::ObjectId]) -> mongodb::action::Delete<'_> {
+        let query = bson::doc! { "_id": { "$in": ids } };
+        self.coll.delete_many(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::TryStreamExt;
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        Result,
+        alerts::Alert,
+        database::{CollectionCollectAll, TestDatabase},
+        models::QueuedAlert,
+        repos::queue_repo::QueueRepo,
+        steam::App,
+    };
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn add_alerts_inserts_alerts_for_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = QueueRepo::new(&db);
+
+        // Sales are tagged by kind with their fields as the content, so they must round-trip.
+        let sale     = Alert::Sale { app: App { app_id: 0, ..Default::default() }, ratings: Default::default(), event: Some("Summer Sale".to_string()) };
+        let expected = QueuedAlert { id: Default::default(), server_id: 0, alert: sale };
+        repo.add_alerts(expected.server_id, std::slice::from_ref(&expected.alert)).await?;
+
+        let actual = db.queue().collect().await?;
+        assert_eq!(1, actual.len(), "Collection has more than one record: {actual:?}");
+        assert_eq!(expected.server_id, actual[0].server_id);
+        assert_eq!(expected.alert, actual[0].alert);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_queued_guild_ids_gets_each_guild_once() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = QueueRepo::new(&db);
+
+        let first  = QueuedAlert { id: Default::default(), server_id: 0, alert: Alert::Released(App { app_id: 0, ..Default::default() }) };
+        let second = QueuedAlert { id: Default::default(), server_id: 0, alert: Alert::Released(App { app_id: 1, ..Default::default() }) };
+        let other  = QueuedAlert { id: Default::default(), server_id: 1, alert: Alert::Released(App { app_id: 0, ..Default::default() }) };
+        db.queue().insert_many([first, second, other]).await?;
+
+        let mut actual = repo.get_queued_guild_ids().await?;
+        actual.sort();
+        assert_eq!([0, 1], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn get_alerts_only_finds_alerts_of_target_guild() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = QueueRepo::new(&db);
+
+        let first  = QueuedAlert { id: Default::default(), server_id: 0, alert: Alert::Released(App { app_id: 0, ..Default::default() }) };
+        let second = QueuedAlert { id: Default::default(), server_id: 0, alert: Alert::Released(App { app_id: 1, ..Default::default() }) };
+        let other  = QueuedAlert { id: Default::default(), server_id: 1, alert: Alert::Released(App { app_id: 0, ..Default::default() }) };
+        db.queue().insert_many([&first, &second, &other]).await?;
+
+        let actual = repo.get_alerts(0).await?.try_collect::<Vec<_>>().await?;
+        assert_eq!([first, second], actual[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial_test::serial(database)]
+    #[rustfmt::skip]
+    async fn remove_alerts_only_deletes_target_alerts() -> Result<()> {
+        let db = TestDatabase::new().await?;
+        let repo = QueueRepo::new(&db);
+
+        let target = QueuedAlert { id: Default::default(), server_id: 0, alert: Alert::Released(App { app_id: 0, ..Default::default() }) };
+        let other  = QueuedAlert { id: Default::default(), server_id: 0, alert: Alert::Released(App { app_id: 1, ..Default::default() }) };
+        db.queue().insert_many([&target, &other]).await?;
+
+        repo.remove_alerts(&[target.id]).await?;
+
+        let actual = db.queue().collect().await?;
+        assert_eq!([other], actual[..]);
+
+        Ok(())
+    }
+}
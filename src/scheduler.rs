@@ -0,0 +1,473 @@
+//! This module provides the [`SCHEDULER`] that runs the bot's periodic jobs,
+//! such as checking apps, and records each job's last run in the database.
+
+use std::{
+    fmt,
+    sync::{Arc, LazyLock},
+    time::Duration,
+};
+
+use anyhow::bail;
+use futures::{FutureExt, future::BoxFuture};
+use mongodb::bson;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::{
+    Result, StdResult, alerts, bindings, calendar, config, digest, events, framework, lease,
+    metrics, models, reminders, shutdown,
+};
+
+pub static SCHEDULER: LazyLock<Scheduler> = LazyLock::new(|| Scheduler::new(jobs()));
+pub const CHECK_APPS: &str = "check_apps";
+pub const REMOVE_ORPHANS: &str = "remove_orphans";
+/// A check can take hours when rate-limited.
+pub(crate) const CHECK_APPS_TIMEOUT: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Registers the bot's jobs.
+fn jobs() -> Vec<Job> {
+    let check = &config::get().check;
+    let minute = Duration::from_secs(60);
+
+    vec![
+        Job::new(
+            CHECK_APPS,
+            "Check tracked apps and alert guilds",
+            Schedule::Daily {
+                hour: check.hour,
+                minute: check.minute,
+            },
+            |ctx, cancel| async move { check_apps(&ctx, &cancel).await }.boxed(),
+        )
+        .timeout(CHECK_APPS_TIMEOUT),
+        // An hour before checking so untracked apps aren't checked.
+        Job::new(
+            REMOVE_ORPHANS,
+            "Remove apps no guild tracks",
+            Schedule::Daily {
+                hour: hour_before(check.hour),
+                minute: check.minute,
+            },
+            |ctx, _| async move { remove_orphans(&ctx).await }.boxed(),
+        )
+        .jitter(10 * minute),
+        Job::new(
+            "check_bindings",
+            "Find guilds whose bound channel is missing",
+            Schedule::Daily {
+                hour: 12,
+                minute: 0,
+            },
+            |ctx, cancel| async move { bindings::check_bindings(&ctx, &cancel).await }.boxed(),
+        )
+        .jitter(30 * minute),
+        // Hourly jobs act on the current hour, so their jitter and timeout stay short.
+        Job::new(
+            "weekly_digests",
+            "Send weekly digests that are due",
+            Schedule::Hourly { minute: 0 },
+            |ctx, cancel| {
+                async move { digest::send_due_weekly_digests(&ctx, &cancel).await }.boxed()
+            },
+        ),
+        Job::new(
+            "sale_event_announcements",
+            "Announce the start of a sale event",
+            Schedule::Hourly { minute: 0 },
+            |ctx, cancel| async move { calendar::announce_sale_event(&ctx, &cancel).await }.boxed(),
+        )
+        .jitter(5 * minute),
+        Job::new(
+            "sale_end_reminders",
+            "Remind guilds of sales about to end",
+            Schedule::Hourly { minute: 0 },
+            |ctx, cancel| {
+                async move { reminders::send_sale_end_reminders(&ctx, &cancel).await }.boxed()
+            },
+        )
+        .jitter(5 * minute),
+        // Alerts queued during a manual run still need the hourly run.
+        Job::new(
+            "queued_alerts",
+            "Deliver alerts queued during quiet hours",
+            Schedule::Hourly { minute: 0 },
+            |ctx, cancel| async move { alerts::deliver_queued(&ctx, &cancel).await }.boxed(),
+        )
+        .overlap(Overlap::Wait),
+    ]
+}
+
+/// The hour before `hour`, wrapping past midnight.
+fn hour_before(hour: u32) -> u32 {
+    (hour + 23) % 24
+}
+
+async fn check_apps(ctx: &framework::Data, cancel: &CancellationToken) -> Result<()> {
+    match events::run_check(ctx, ctx.dry_run, cancel).await {
+        Some(res) => res.map(|_| ()),
+        None => bail!("A check is already running"),
+    }
+}
+
+async fn remove_orphans(ctx: &framework::Data) -> Result<()> {
+    if ctx.dry_run {
+        info!("Skipping removing orphans in dry run mode");
+        return Ok(());
+    }
+    let removed = ctx.repo.apps.remove_orphans().await?;
+    info!(removed, "Removed orphaned apps");
+    Ok(())
+}
+
+/// When a job is due, in UTC.
+#[derive(Debug, Clone, Copy)]
+pub enum Schedule {
+    /// Every day at `hour`:`minute`.
+    Daily { hour: u32, minute: u32 },
+    /// Every hour at `minute`.
+    Hourly { minute: u32 },
+}
+
+impl Schedule {
+    /// The first time after `after` that the job is due.
+    pub fn next_after(
+        &self,
+        after: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::DateTime<chrono::Utc> {
+        use chrono::Timelike;
+
+        let (candidate, period) = match *self {
+            Schedule::Daily { hour, minute } => (
+                after.date_naive().and_hms_opt(hour, minute, 0),
+                chrono::TimeDelta::days(1),
+            ),
+            Schedule::Hourly { minute } => (
+                after.date_naive().and_hms_opt(after.hour(), minute, 0),
+                chrono::TimeDelta::hours(1),
+            ),
+        };
+        let candidate = candidate.expect("schedule should have valid hms").and_utc();
+
+        if candidate > after {
+            candidate
+        } else {
+            candidate + period
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Daily { hour, minute } => write!(f, "daily at {hour:02}:{minute:02} UTC"),
+            Schedule::Hourly { minute } => write!(f, "hourly at :{minute:02}"),
+        }
+    }
+}
+
+/// What to do when a job is due while its previous run hasn't finished.
+#[derive(Debug, Clone, Copy, strum_macros::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum Overlap {
+    /// Skip the run.
+    Skip,
+    /// Run once the previous run finishes.
+    Wait,
+}
+
+/// Error variants when triggering a job using [`Scheduler::trigger`].
+#[derive(Debug, thiserror::Error)]
+pub enum TriggerError {
+    #[error("There's no job named {0}")]
+    UnknownJob(String),
+    #[error("{0} is already running")]
+    AlreadyRunning(&'static str),
+    #[error("This process doesn't hold the lease on scheduled jobs")]
+    NotLeaseHolder,
+}
+
+/// Returned by jobs that stopped early because their run was cancelled.
+#[derive(Debug, thiserror::Error)]
+#[error("Cancelled before finishing")]
+pub struct Cancelled;
+
+/// Runs a job. The token is cancelled on shutdown or timeout, after which the job
+/// should stop where it can do so safely and return [`Cancelled`].
+type JobFn = fn(Arc<framework::Data>, CancellationToken) -> BoxFuture<'static, Result<()>>;
+
+pub struct Job {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub schedule: Schedule,
+    /// Up to this long is randomly added to each scheduled start so jobs due at
+    /// the same time don't all start at once.
+    pub jitter: Duration,
+    /// Runs taking longer than this are cancelled. They're recorded as timed out
+    /// once they stop.
+    pub timeout: Duration,
+    pub overlap: Overlap,
+    run: JobFn,
+    /// Held while the job runs.
+    running: tokio::sync::Mutex<()>,
+}
+
+impl Job {
+    /// Creates a job without jitter that times out after 30 minutes and skips
+    /// overlapping runs.
+    fn new(name: &'static str, description: &'static str, schedule: Schedule, run: JobFn) -> Self {
+        Self {
+            name,
+            description,
+            schedule,
+            jitter: Duration::ZERO,
+            timeout: Duration::from_secs(30 * 60),
+            overlap: Overlap::Skip,
+            run,
+            running: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn overlap(mut self, overlap: Overlap) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.try_lock().is_err()
+    }
+
+    /// When the job is next due, not counting jitter.
+    pub fn next_run_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.schedule.next_after(chrono::Utc::now())
+    }
+
+    /// Runs the job whenever it's due while this process holds the lease, until shutdown.
+    async fn run_on_schedule(&self, ctx: Arc<framework::Data>) {
+        let mut after = chrono::Utc::now();
+        loop {
+            let due = self.schedule.next_after(after);
+            let jitter = rand::random_range(Duration::ZERO..=self.jitter);
+            let until = (due - chrono::Utc::now()).to_std().unwrap_or_default() + jitter;
+            tokio::select! {
+                _ = tokio::time::sleep(until) => {}
+                _ = shutdown::SHUTDOWN.cancelled() => break,
+            }
+            // Waking early must not make the same slot due again.
+            after = due.max(chrono::Utc::now());
+
+            if !lease::LEASE.is_held() {
+                continue;
+            }
+            self.run(ctx.clone(), models::JobTrigger::Scheduled).await;
+        }
+    }
+
+    /// Runs the job and records the run, unless the previous run hasn't finished
+    /// and overlapping runs are skipped, in which case `None` is returned.
+    async fn run(
+        &self,
+        ctx: Arc<framework::Data>,
+        trigger: models::JobTrigger,
+    ) -> Option<models::JobStatus> {
+        let _guard = match self.overlap {
+            Overlap::Skip => match self.running.try_lock() {
+                Ok(guard) => guard,
+                Err(_) => {
+                    warn!(
+                        job = self.name,
+                        "Skipping run since the previous run hasn't finished"
+                    );
+                    return None;
+                }
+            },
+            Overlap::Wait => self.running.lock().await,
+        };
+
+        info!(job = self.name, %trigger, "Running job");
+        let run = models::JobRun {
+            job: self.name.to_string(),
+            holder: lease::LEASE.holder().to_string(),
+            trigger,
+            status: models::JobStatus::Running,
+            started_at: bson::DateTime::now(),
+            finished_at: None,
+            error: None,
+        };
+        ctx.repo
+            .jobs
+            .start_run(&run)
+            .await
+            .inspect_err(|err| error!(?err, job = self.name, "Failed to record job start"))
+            .ok();
+
+        // The job is never dropped midway, so it can't leave state half-updated.
+        let cancel = shutdown::SHUTDOWN.child_token();
+        let mut job = (self.run)(ctx.clone(), cancel.clone());
+        let mut timed_out = false;
+        let res = tokio::select! {
+            res = &mut job => res,
+            _ = tokio::time::sleep(self.timeout) => {
+                warn!(job = self.name, "Job timed out. Waiting for it to stop...");
+                timed_out = true;
+                cancel.cancel();
+                job.await
+            }
+        };
+        let (status, err) = match res {
+            _ if timed_out => {
+                error!(job = self.name, "Job timed out");
+                let err = format!("Timed out after {}s", self.timeout.as_secs());
+                (models::JobStatus::TimedOut, Some(err))
+            }
+            Ok(()) => (models::JobStatus::Succeeded, None),
+            Err(err) if err.is::<Cancelled>() => {
+                warn!(job = self.name, "Job was interrupted");
+                (models::JobStatus::Interrupted, Some(format!("{err:#}")))
+            }
+            Err(err) => {
+                error!(?err, job = self.name, "Job failed");
+                (models::JobStatus::Failed, Some(format!("{err:#}")))
+            }
+        };
+        info!(job = self.name, %status, "Finished job");
+        metrics::METRICS
+            .job_runs
+            .with_label_values(&[self.name, &status.to_string()])
+            .inc();
+        ctx.repo
+            .jobs
+            .finish_run(&run, status, bson::DateTime::now(), err.as_deref())
+            .await
+            .inspect_err(|err| error!(?err, job = self.name, "Failed to record job finish"))
+            .ok();
+
+        Some(status)
+    }
+}
+
+pub struct Scheduler {
+    jobs: Vec<Job>,
+}
+
+impl Scheduler {
+    fn new(jobs: Vec<Job>) -> Self {
+        Self { jobs }
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    pub fn job(&self, name: &str) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.name == name)
+    }
+
+    /// Runs each job on its schedule until shutdown.
+    pub fn start(&'static self, ctx: Arc<framework::Data>) {
+        for job in &self.jobs {
+            let ctx = ctx.clone();
+            tokio::spawn(async move { job.run_on_schedule(ctx).await });
+        }
+        tokio::spawn(async move { self.resume_interrupted_checks(ctx).await });
+    }
+
+    /// Whenever this process acquires the lease, resumes the check the previous
+    /// holder was interrupted during, if any, until shutdown.
+    async fn resume_interrupted_checks(&'static self, ctx: Arc<framework::Data>) {
+        let job = self
+            .job(CHECK_APPS)
+            .expect("check job should be registered");
+        loop {
+            tokio::select! {
+                _ = lease::LEASE.acquired() => {}
+                _ = shutdown::SHUTDOWN.cancelled() => break,
+            }
+            // Dry runs don't resume checks.
+            if ctx.dry_run {
+                continue;
+            }
+
+            match events::interrupted_check(&ctx).await {
+                Ok(Some(_)) => {
+                    let ctx = ctx.clone();
+                    tokio::spawn(async move { job.run(ctx, models::JobTrigger::Resumed).await });
+                }
+                Ok(None) => {}
+                Err(err) => error!(?err, "Failed to get interrupted check"),
+            }
+        }
+    }
+
+    /// Starts running the job named `name` now, regardless of its schedule.
+    pub fn trigger(
+        &'static self,
+        name: &str,
+        ctx: Arc<framework::Data>,
+    ) -> StdResult<(), TriggerError> {
+        let job = self
+            .job(name)
+            .ok_or_else(|| TriggerError::UnknownJob(name.to_string()))?;
+        // Otherwise another process could run the job at the same time.
+        if !lease::LEASE.is_held() {
+            return Err(TriggerError::NotLeaseHolder);
+        }
+        if matches!(job.overlap, Overlap::Skip) && job.is_running() {
+            return Err(TriggerError::AlreadyRunning(job.name));
+        }
+
+        tokio::spawn(async move { job.run(ctx, models::JobTrigger::Manual).await });
+        Ok(())
+    }
+
+    /// Waits for running jobs to finish.
+    pub async fn wait_for_jobs(&self) {
+        for job in &self.jobs {
+            drop(job.running.lock().await);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::scheduler::{Schedule, hour_before};
+
+    #[rstest]
+    #[case(17, 16)]
+    #[case(1, 0)]
+    #[case(0, 23)]
+    fn hour_before_wraps_past_midnight(#[case] hour: u32, #[case] expected: u32) {
+        assert_eq!(expected, hour_before(hour));
+    }
+
+    #[rstest]
+    #[case::later_today(Schedule::Daily { hour: 17, minute: 0 }, "2026-10-19T09:00:00Z", "2026-10-19T17:00:00Z")]
+    #[case::tomorrow(Schedule::Daily { hour: 17, minute: 0 }, "2026-10-19T18:00:00Z", "2026-10-20T17:00:00Z")]
+    #[case::due_now_is_next_day(Schedule::Daily { hour: 17, minute: 0 }, "2026-10-19T17:00:00Z", "2026-10-20T17:00:00Z")]
+    #[case::slot_before_midnight(Schedule::Daily { hour: 23, minute: 30 }, "2026-10-19T23:45:00Z", "2026-10-20T23:30:00Z")]
+    #[case::later_this_hour(Schedule::Hourly { minute: 30 }, "2026-10-19T09:10:00Z", "2026-10-19T09:30:00Z")]
+    #[case::next_hour(Schedule::Hourly { minute: 0 }, "2026-10-19T09:10:00Z", "2026-10-19T10:00:00Z")]
+    #[case::due_now_is_next_hour(Schedule::Hourly { minute: 0 }, "2026-10-19T09:00:00Z", "2026-10-19T10:00:00Z")]
+    #[case::across_midnight(Schedule::Hourly { minute: 0 }, "2026-10-19T23:59:59Z", "2026-10-20T00:00:00Z")]
+    fn next_after_finds_first_slot_after(
+        #[case] schedule: Schedule,
+        #[case] after: &str,
+        #[case] expected: &str,
+    ) {
+        let after = after.parse().unwrap();
+        let expected = expected.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+        assert_eq!(expected, schedule.next_after(after));
+    }
+}
@@ -0,0 +1,35 @@
+//! This module provides the [`SHUTDOWN`] token cancelled once the bot is
+//! asked to stop, such as by Docker's SIGTERM.
+
+use std::sync::LazyLock;
+
+use tokio_util::sync::CancellationToken;
+
+/// Cancelled when shutdown begins. Long-running work should stop at the next
+/// point where it can do so without leaving state half-updated.
+pub static SHUTDOWN: LazyLock<CancellationToken> = LazyLock::new(CancellationToken::new);
+
+pub fn is_shutting_down() -> bool {
+    SHUTDOWN.is_cancelled()
+}
+
+/// Waits for SIGTERM or Ctrl+C, then cancels [`SHUTDOWN`].
+pub async fn wait_for_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut terminate =
+            signal(SignalKind::terminate()).expect("should install SIGTERM handler");
+        tokio::select! {
+            _ = terminate.recv() => {}
+            _ = tokio::signal::ctrl_c() => {}
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await.ok();
+    }
+
+    SHUTDOWN.cancel();
+}
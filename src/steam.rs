@@ -2,9 +2,11 @@
 
 use std::sync::Arc;
 
+use futures::{StreamExt, stream};
 use serde::Deserialize;
+use tracing::error;
 
-use crate::StdResult;
+use crate::{StdResult, metrics};
 
 /// Error variants when fetching Steam apps using [`Client::fetch_app`].
 #[derive(Debug, thiserror::Error)]
@@ -18,12 +20,22 @@ pub enum FetchError {
 }
 
 impl FetchError {
+    /// The variant's name for metrics.
+    pub fn variant(&self) -> &'static str {
+        match self {
+            FetchError::Http(_) if self.is_rate_limited() => "rate_limited",
+            FetchError::Http(_) => "http",
+            FetchError::MissingJsonField => "missing_json_field",
+            FetchError::JsonDeserialize(_) => "json_deserialize",
+        }
+    }
+
     pub fn is_rate_limited(&self) -> bool {
         matches!(self, FetchError::Http(error) if error.status() == Some(reqwest::StatusCode::TOO_MANY_REQUESTS))
     }
 }
 
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct App {
     pub name: String,
     #[serde(rename = "steam_appid")]
@@ -35,23 +47,117 @@ pub struct App {
     pub price_overview: Option<PriceOverview>,
     pub recommendations: Option<Recommendations>,
     pub release_date: ReleaseDate,
+    #[serde(default)]
+    pub developers: Vec<String>,
+    #[serde(default)]
+    pub publishers: Vec<String>,
+    #[serde(default)]
+    pub genres: Vec<Genre>,
+    /// App ids of the app's DLC.
+    #[serde(default)]
+    pub dlc: Vec<i32>,
+    /// The base game if the app is DLC.
+    #[serde(default)]
+    pub fullgame: Option<FullGame>,
 }
 
-#[derive(Debug, Clone, serde::Deserialize)]
+impl App {
+    /// Steam lists Early Access as a genre rather than a release state.
+    pub fn is_early_access(&self) -> bool {
+        const EARLY_ACCESS_GENRE_ID: &str = "70";
+        self.genres.iter().any(|g| g.id == EARLY_ACCESS_GENRE_ID)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct PriceOverview {
     pub discount_percent: i32,
+    /// Price before discount in the currency's minor units, e.g. cents.
+    #[serde(rename = "initial")]
+    pub initial_price: i64,
+    /// Price after discount in the currency's minor units, e.g. cents.
+    #[serde(rename = "final")]
+    pub final_price: i64,
     pub initial_formatted: String,
     pub final_formatted: String,
+    /// Unix timestamp of when the discount ends. Only present for some sales.
+    #[serde(default)]
+    pub discount_end_date: Option<i64>,
 }
 
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct Recommendations {
     pub total: u32,
 }
 
-#[derive(Debug, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct FullGame {
+    #[serde(rename = "appid", deserialize_with = "maybe_str_parse")]
+    pub app_id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Genre {
+    pub id: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct ReleaseDate {
     pub coming_soon: bool,
+    #[serde(default)]
+    pub date: String,
+}
+
+/// Review and Steam Deck information that isn't part of an app's details.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct Ratings {
+    pub reviews: Option<ReviewSummary>,
+    pub deck: Option<DeckCompatibility>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ReviewSummary {
+    /// Steam's rating of the reviews, e.g. "Very Positive".
+    pub review_score_desc: String,
+    pub total_positive: u32,
+    pub total_negative: u32,
+}
+
+impl ReviewSummary {
+    /// Percentage of positive reviews. `None` if there are no reviews.
+    pub fn positive_percent(&self) -> Option<i32> {
+        let total = self.total_positive + self.total_negative;
+        (total > 0).then(|| (self.total_positive as f64 / total as f64 * 100.0).round() as i32)
+    }
+}
+
+/// Steam Deck compatibility category as determined by Valve's review.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize, strum_macros::Display,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DeckCompatibility {
+    Unknown,
+    Unsupported,
+    Playable,
+    Verified,
+}
+
+impl DeckCompatibility {
+    fn from_category(category: u64) -> Self {
+        match category {
+            1 => Self::Unsupported,
+            2 => Self::Playable,
+            3 => Self::Verified,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn is_playable(&self) -> bool {
+        matches!(self, Self::Playable | Self::Verified)
+    }
 }
 
 #[derive(Debug, Clone, derivative::Derivative, serde::Deserialize)]
@@ -71,24 +177,38 @@ pub struct Client {
     store_base: Arc<String>,
     /// Base url for the community endpoint.
     community_base: Arc<String>,
+    /// Max concurrent requests in [`Client::app_details_batch`].
+    fetch_buffer_size: usize,
 }
 
 impl Client {
-    pub fn new(store_base: impl Into<String>, community_base: impl Into<String>) -> Self {
+    pub fn new(
+        store_base: impl Into<String>,
+        community_base: impl Into<String>,
+        fetch_buffer_size: usize,
+    ) -> Self {
         Self {
             http: reqwest::Client::new(),
             store_base: Arc::new(store_base.into()),
             community_base: Arc::new(community_base.into()),
+            fetch_buffer_size,
         }
     }
 
+    #[tracing::instrument(level = "info", skip(self))]
     pub async fn app_details(&self, app_id: i32) -> StdResult<Option<App>, FetchError> {
+        self.fetch_app_details(app_id)
+            .await
+            .inspect_err(count_fetch_error)
+    }
+
+    async fn fetch_app_details(&self, app_id: i32) -> StdResult<Option<App>, FetchError> {
         let app_id = app_id.to_string();
         let url = format!("{}/api/appdetails", self.store_base);
         let query = [
             (
                 "filters",
-                "basic,price_overview,recommendations,release_date",
+                "basic,dlc,price_overview,recommendations,release_date,developers,publishers,genres",
             ),
             ("cc", "US"),
             ("appids", &app_id),
@@ -119,7 +239,129 @@ impl Client {
         Ok(Some(serde_json::from_value(data)?))
     }
 
-    pub async fn search_apps(&self, query: &str) -> StdResult<Vec<SearchResult>, reqwest::Error> {
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn app_reviews(&self, app_id: i32) -> StdResult<Option<ReviewSummary>, FetchError> {
+        self.fetch_app_reviews(app_id)
+            .await
+            .inspect_err(count_fetch_error)
+    }
+
+    async fn fetch_app_reviews(&self, app_id: i32) -> StdResult<Option<ReviewSummary>, FetchError> {
+        let url = format!("{}/appreviews/{app_id}", self.store_base);
+        let query = [
+            ("json", "1"),
+            ("num_per_page", "0"),
+            ("language", "all"),
+            ("purchase_type", "all"),
+        ];
+
+        let res = self
+            .http
+            .get(url)
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?;
+        let mut body = res.json::<serde_json::Value>().await?;
+
+        let success = body
+            .get("success")
+            .and_then(|s| s.as_i64())
+            .ok_or(FetchError::MissingJsonField)?;
+        if success != 1 {
+            return Ok(None);
+        }
+
+        let summary = body
+            .get_mut("query_summary")
+            .ok_or(FetchError::MissingJsonField)?
+            .take();
+        Ok(Some(serde_json::from_value(summary)?))
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn deck_compatibility(
+        &self,
+        app_id: i32,
+    ) -> StdResult<Option<DeckCompatibility>, FetchError> {
+        self.fetch_deck_compatibility(app_id)
+            .await
+            .inspect_err(count_fetch_error)
+    }
+
+    async fn fetch_deck_compatibility(
+        &self,
+        app_id: i32,
+    ) -> StdResult<Option<DeckCompatibility>, FetchError> {
+        let url = format!(
+            "{}/saleaction/ajaxgetdeckappcompatibilityreport",
+            self.store_base
+        );
+        let app_id = app_id.to_string();
+        let query = [("nAppID", app_id.as_str())];
+
+        let res = self
+            .http
+            .get(url)
+            .query(&query)
+            .send()
+            .await?
+            .error_for_status()?;
+        let body = res.json::<serde_json::Value>().await?;
+
+        let success = body
+            .get("success")
+            .and_then(|s| s.as_i64())
+            .ok_or(FetchError::MissingJsonField)?;
+        if success != 1 {
+            return Ok(None);
+        }
+
+        let category = body
+            .get("results")
+            .and_then(|r| r.get("resolved_category"))
+            .and_then(|c| c.as_u64())
+            .ok_or(FetchError::MissingJsonField)?;
+        Ok(Some(DeckCompatibility::from_category(category)))
+    }
+
+    /// Fetches the details of multiple apps with a bounded number of concurrent requests.
+    /// Apps that don't exist or fail to fetch are omitted. Stops early if rate-limited,
+    /// in which case the returned bool is true.
+    pub async fn app_details_batch(&self, app_ids: Vec<i32>) -> (Vec<App>, bool) {
+        let fetches = stream::iter(
+            app_ids
+                .into_iter()
+                .map(|app_id| async move { (app_id, self.app_details(app_id).await) }),
+        );
+        let mut fetch_stream = fetches.buffer_unordered(self.fetch_buffer_size);
+
+        let mut apps = Vec::new();
+        let mut rate_limited = false;
+        while let Some((app_id, app)) = fetch_stream.next().await {
+            match app {
+                Ok(Some(app)) => apps.push(app),
+                Ok(None) => { /* App doesn't exist for given app_id */ }
+                Err(err) if err.is_rate_limited() => {
+                    rate_limited = true;
+                    break;
+                }
+                Err(err) => error!(app_id, ?err, "Failed to fetch app"),
+            }
+        }
+
+        (apps, rate_limited)
+    }
+
+    #[tracing::instrument(level = "info", skip(self))]
+    pub async fn search_apps(&self, query: &str) -> StdResult<Vec<SearchResult>, FetchError> {
+        self.fetch_search_apps(query)
+            .await
+            .map_err(FetchError::from)
+            .inspect_err(count_fetch_error)
+    }
+
+    async fn fetch_search_apps(&self, query: &str) -> reqwest::Result<Vec<SearchResult>> {
         let url = format!(
             "{}/actions/SearchApps/{}",
             self.community_base,
@@ -129,6 +371,22 @@ impl Client {
     }
 }
 
+fn count_fetch_error(err: &FetchError) {
+    metrics::METRICS
+        .fetch_errors
+        .with_label_values(&[err.variant()])
+        .inc();
+}
+
+/// Parses a release date as displayed by Steam if it's a concrete day, e.g.
+/// "Aug 14, 2026". Vague dates such as "Q3 2026" or "Coming soon" are `None`.
+pub fn parse_release_date(date: &str) -> Option<chrono::NaiveDate> {
+    const FORMATS: [&str; 2] = ["%b %d, %Y", "%d %b, %Y"];
+    FORMATS
+        .iter()
+        .find_map(|format| chrono::NaiveDate::parse_from_str(date.trim(), format).ok())
+}
+
 fn str_parse<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -138,3 +396,104 @@ where
     let s = String::deserialize(deserializer)?;
     s.parse().map_err(serde::de::Error::custom)
 }
+
+/// Like [`str_parse`], but also accepts an already parsed value, such as one
+/// that was serialized after being parsed.
+fn maybe_str_parse<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr + Deserialize<'de>,
+    T::Err: std::fmt::Display,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MaybeStr<T> {
+        Str(String),
+        Parsed(T),
+    }
+
+    match MaybeStr::<T>::deserialize(deserializer)? {
+        MaybeStr::Str(s) => s.parse().map_err(serde::de::Error::custom),
+        MaybeStr::Parsed(x) => Ok(x),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::steam::{App, PriceOverview, parse_release_date};
+
+    /// The `data` of an appdetails response, trimmed to the fields that are parsed.
+    const APP_DETAILS: &str = r#"{
+        "type": "game",
+        "name": "Portal 2",
+        "steam_appid": 620,
+        "required_age": 0,
+        "is_free": false,
+        "dlc": [323180],
+        "short_description": "The &quot;Perpetual Testing Initiative&quot; has been expanded to allow you to design co-op puzzles for you and your friends!",
+        "header_image": "https://shared.akamai.steamstatic.com/store_item_assets/steam/apps/620/header.jpg",
+        "developers": ["Valve"],
+        "publishers": ["Valve"],
+        "price_overview": {
+            "currency": "USD",
+            "initial": 999,
+            "final": 99,
+            "discount_percent": 90,
+            "initial_formatted": "$9.99",
+            "final_formatted": "$0.99"
+        },
+        "genres": [
+            { "id": "1", "description": "Action" },
+            { "id": "25", "description": "Adventure" }
+        ],
+        "recommendations": { "total": 389571 },
+        "release_date": { "coming_soon": false, "date": "Apr 18, 2011" }
+    }"#;
+
+    #[test]
+    fn app_details_parses_price_overview() {
+        let app = serde_json::from_str::<App>(APP_DETAILS).unwrap();
+
+        let expected = PriceOverview {
+            discount_percent: 90,
+            initial_price: 999,
+            final_price: 99,
+            initial_formatted: "$9.99".to_string(),
+            final_formatted: "$0.99".to_string(),
+            discount_end_date: None,
+        };
+        assert_eq!(Some(expected), app.price_overview);
+    }
+
+    #[test]
+    fn price_overview_round_trips() {
+        let app = serde_json::from_str::<App>(APP_DETAILS).unwrap();
+        let price = app.price_overview.unwrap();
+
+        let json = serde_json::to_string(&price).unwrap();
+        assert_eq!(price, serde_json::from_str::<PriceOverview>(&json).unwrap());
+    }
+
+    #[rstest]
+    #[case::month_first("Aug 14, 2026", Some((2026, 8, 14)))]
+    #[case::day_first("14 Aug, 2026", Some((2026, 8, 14)))]
+    #[case::single_digit_day("Mar 5, 2027", Some((2027, 3, 5)))]
+    #[case::padded(" Aug 14, 2026 ", Some((2026, 8, 14)))]
+    #[case::coming_soon("Coming soon", None)]
+    #[case::to_be_announced("To be announced", None)]
+    #[case::quarter("Q1 2027", None)]
+    #[case::month("August 2026", None)]
+    #[case::year("2027", None)]
+    #[case::localized("14 août 2026", None)]
+    #[case::empty("", None)]
+    fn parse_release_date_only_parses_concrete_days(
+        #[case] date: &str,
+        #[case] expected: Option<(i32, u32, u32)>,
+    ) {
+        let expected = expected.map(|(y, m, d)| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap());
+        assert_eq!(expected, parse_release_date(date));
+    }
+}
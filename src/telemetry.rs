@@ -0,0 +1,125 @@
+//! This module provides [`init`] for setting up log output and exporting
+//! traces to an OpenTelemetry collector, and [`command_spans`] for tracing
+//! database commands.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use mongodb::event::{EventHandler, command::CommandEvent};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_sdk::{Resource, trace::SdkTracerProvider};
+use tracing_subscriber::{EnvFilter, Layer, layer::SubscriberExt, util::SubscriberInitExt};
+
+use crate::{Result, util};
+
+const DEFAULT_SERVICE_NAME: &str = "steamsale_bot";
+
+/// Format of log lines written to stdout.
+#[derive(Debug, Default, Clone, Copy, serde::Deserialize, strum_macros::EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines.
+    #[default]
+    Text,
+    /// JSON lines for log shippers.
+    Json,
+}
+
+/// Keeps the trace exporter alive. Call [`Telemetry::shutdown`] before exiting
+/// so buffered spans are exported.
+pub struct Telemetry {
+    provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    pub fn shutdown(self) {
+        if let Some(provider) = self.provider
+            && let Err(err) = provider.shutdown()
+        {
+            // Only the exporter is shut down, so logs are still written.
+            tracing::error!(?err, "Failed to shut down trace exporter");
+        }
+    }
+}
+
+/// Installs the global tracing subscriber. Logs are written in `format` and
+/// spans are exported over OTLP if `OTEL_EXPORTER_OTLP_ENDPOINT` is set. Both
+/// are filtered by `RUST_LOG`.
+pub fn init(format: LogFormat) -> Result<Telemetry> {
+    let provider = match util::env_var::<String>("OTEL_EXPORTER_OTLP_ENDPOINT") {
+        // The exporter reads the endpoint and other OTEL_* options itself.
+        Ok(_) => Some(tracer_provider()?),
+        Err(util::EnvVarError::InvalidOrMissingKey { .. }) => None,
+        Err(err) => Err(err)?,
+    };
+
+    let fmt_layer = match format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer().json().boxed(),
+    };
+    let otel_layer = provider.as_ref().map(|provider| {
+        tracing_opentelemetry::layer().with_tracer(provider.tracer(DEFAULT_SERVICE_NAME))
+    });
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::from_default_env())
+        .with(fmt_layer)
+        .with(otel_layer)
+        .init();
+
+    Ok(Telemetry { provider })
+}
+
+fn tracer_provider() -> Result<SdkTracerProvider> {
+    let service_name = match util::env_var("OTEL_SERVICE_NAME") {
+        Ok(x) => x,
+        Err(util::EnvVarError::InvalidOrMissingKey { .. }) => DEFAULT_SERVICE_NAME.to_string(),
+        Err(err) => Err(err)?,
+    };
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .build()?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name).build())
+        .build())
+}
+
+/// Records each database command as a `mongodb.command` span under the span
+/// that ran it. The driver's own `mongodb::command` tracing only emits events,
+/// which are exported as events of whichever span is current, not as spans.
+pub fn command_spans() -> EventHandler<CommandEvent> {
+    // Spans of commands that haven't finished by request id.
+    let spans = Mutex::new(HashMap::new());
+
+    EventHandler::callback(move |event| {
+        let mut spans = spans
+            .lock()
+            .expect("command spans lock shouldn't be poisoned");
+        match event {
+            CommandEvent::Started(event) => {
+                let span = tracing::info_span!(
+                    "mongodb.command",
+                    otel.kind = "client",
+                    otel.status_code = tracing::field::Empty,
+                    db.system = "mongodb",
+                    db.name = event.db,
+                    db.operation = event.command_name,
+                    error = tracing::field::Empty,
+                );
+                spans.insert(event.request_id, span);
+            }
+            CommandEvent::Succeeded(event) => {
+                spans.remove(&event.request_id);
+            }
+            CommandEvent::Failed(event) => {
+                if let Some(span) = spans.remove(&event.request_id) {
+                    span.record("otel.status_code", "ERROR");
+                    span.record("error", tracing::field::display(&event.failure));
+                }
+            }
+            _ => {}
+        }
+    })
+}
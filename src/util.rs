@@ -3,6 +3,7 @@
 use std::sync::Arc;
 
 use anyhow::Context;
+use mongodb::bson;
 use poise::serenity_prelude as serenity;
 use tracing::{error, warn};
 
@@ -128,6 +129,50 @@ pub fn parse_csv_app_ids(x: &str) -> StdResult<Vec<i32>, std::num::ParseIntError
     })
 }
 
+pub const PARSE_PRICE_FAIL_MSG: &str = "Failed to parse price. \
+Please enter a positive amount with up to two decimals, without the currency symbol. \
+Ex: `19.99`";
+
+/// Parses a price such as `19.99` into the currency's minor units, e.g. cents.
+pub fn parse_price(x: &str) -> Option<i64> {
+    let x = x.trim();
+    let (whole, fraction) = x.split_once('.').unwrap_or((x, ""));
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > 2
+        || !is_digits(whole)
+        || !is_digits(fraction)
+    {
+        return None;
+    }
+
+    let whole: i64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: i64 = format!("{fraction:0<2}").parse().ok()?;
+    let price = whole.checked_mul(100)?.checked_add(fraction)?;
+    (price > 0).then_some(price)
+}
+
+/// Formats a price in the currency's minor units, e.g. cents, as `19.99`.
+pub fn format_price(minor_units: i64) -> String {
+    let sign = if minor_units < 0 { "-" } else { "" };
+    let minor_units = minor_units.unsigned_abs();
+    format!("{sign}{}.{:02}", minor_units / 100, minor_units % 100)
+}
+
+/// Converts a BSON datetime to a chrono one.
+pub fn to_chrono(x: bson::DateTime) -> chrono::DateTime<chrono::Utc> {
+    x.to_system_time().into()
+}
+
+/// Converts a chrono datetime to a BSON one, truncated to milliseconds.
+pub fn from_chrono(x: chrono::DateTime<chrono::Utc>) -> bson::DateTime {
+    bson::DateTime::from_system_time(x.into())
+}
+
 pub trait PoiseData {
     async fn poise_data_unwrap(&self) -> Arc<framework::Data>;
 }
@@ -147,3 +192,60 @@ impl PoiseData for serenity::Context {
             .clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::util::{format_price, parse_price};
+
+    #[rstest]
+    #[case("19.99", Some(1999))]
+    #[case("19.9", Some(1990))]
+    #[case("19", Some(1900))]
+    #[case("19.", Some(1900))]
+    #[case(".5", Some(50))]
+    #[case(" 0.01 ", Some(1))]
+    #[case("0", None)]
+    #[case("0.00", None)]
+    #[case("", None)]
+    #[case(".", None)]
+    #[case("19.999", None)]
+    #[case("-1", None)]
+    #[case("-1.00", None)]
+    #[case("1,000", None)]
+    #[case("1,00", None)]
+    #[case("1.000,00", None)]
+    #[case("$19.99", None)]
+    #[case("1e3", None)]
+    #[case("92233720368547758.07", Some(i64::MAX))]
+    #[case("92233720368547758.08", None)]
+    #[case("99999999999999999999", None)]
+    fn parse_price_parses_minor_units(#[case] input: &str, #[case] expected: Option<i64>) {
+        assert_eq!(expected, parse_price(input));
+    }
+
+    #[rstest]
+    #[case(1999, "19.99")]
+    #[case(1990, "19.90")]
+    #[case(5, "0.05")]
+    #[case(0, "0.00")]
+    #[case(-5, "-0.05")]
+    #[case(-1999, "-19.99")]
+    #[case(i64::MAX, "92233720368547758.07")]
+    #[case(i64::MIN, "-92233720368547758.08")]
+    fn format_price_formats_major_units(#[case] input: i64, #[case] expected: &str) {
+        assert_eq!(expected, format_price(input));
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(99)]
+    #[case(100)]
+    #[case(1999)]
+    #[case(i64::MAX)]
+    fn parse_price_round_trips_format_price(#[case] minor_units: i64) {
+        assert_eq!(Some(minor_units), parse_price(&format_price(minor_units)));
+    }
+}